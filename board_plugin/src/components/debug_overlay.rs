@@ -0,0 +1,9 @@
+use bevy::prelude::Component;
+
+/// Sprite over the board drawing the debug overlay tiles, one texture for the whole board
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct DebugTiles;
+
+/// Text panel of the debug overlay
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct DebugPanel;
@@ -1,9 +1,19 @@
 pub use coordinates::Coordinates;
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
-pub use uncover::Uncover;
+pub use tile_cursor_highlight::TileCursorHighlight;
+pub use themed::Themed;
+pub use tile_renderer::{TileChunk, TileSprites};
+pub use tile_feedback::{PressedTile, TileHover};
+#[cfg(all(feature = "debug", debug_assertions))]
+pub use debug_overlay::{DebugPanel, DebugTiles};
 
 pub mod coordinates;
 mod bomb;
 mod bomb_neighbor;
-mod uncover;
+mod tile_cursor_highlight;
+mod themed;
+mod tile_renderer;
+mod tile_feedback;
+#[cfg(all(feature = "debug", debug_assertions))]
+mod debug_overlay;
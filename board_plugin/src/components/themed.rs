@@ -0,0 +1,9 @@
+use bevy::prelude::Component;
+
+/// Sprite of the board drawn from a `BoardAssets` material, restyled in place when the theme
+/// changes. Tiles follow the theme through its tile atlas instead
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum Themed {
+    Background,
+    Cursor,
+}
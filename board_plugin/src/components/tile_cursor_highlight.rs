@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Sprite highlighting the tile under the `TileCursor`
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct TileCursorHighlight;
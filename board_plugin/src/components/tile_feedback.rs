@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+/// Sprite lighting up the covered tile under the mouse
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct TileHover;
+
+/// One of the sprites drawing the tiles of the `TilePress` pressed in, over the tiles themselves
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct PressedTile;
@@ -0,0 +1,17 @@
+use bevy::prelude::{Component, Entity};
+
+/// Sprite entity of every tile of a board drawn with `TileRenderer::Sprites`, row by row from
+/// the bottom. Set on the board entity
+#[derive(Debug, Clone, Component)]
+pub struct TileSprites(pub Vec<Entity>);
+
+/// Mesh drawing a rectangle of tiles of a board drawn with `TileRenderer::Chunks`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct TileChunk {
+    /// Chunk position in the chunk grid
+    pub x: u16,
+    pub y: u16,
+    /// Tiles drawn, chunks on the top and right edges may be narrower
+    pub width: u16,
+    pub height: u16,
+}
@@ -1,4 +1,5 @@
 use crate::components::Coordinates;
+use crate::resources::TileState;
 
 #[derive(Debug, Copy, Clone)]
 pub struct TileTriggerEvent(pub Coordinates);
@@ -13,4 +14,29 @@ pub struct BombExplosionEvent;
 pub struct TileMarkEvent(pub Coordinates);
 
 #[derive(Debug, Copy, Clone)]
-pub struct UncoverAdjacentTilesEvent(pub Coordinates);
\ No newline at end of file
+pub struct UncoverAdjacentTilesEvent(pub Coordinates);
+
+/// Sent the first time a tile of an opening is uncovered
+#[derive(Debug, Copy, Clone)]
+pub struct OpeningClearedEvent(pub Coordinates);
+
+/// Asks the board to uncover a tile that can be proven safe
+#[derive(Debug, Copy, Clone)]
+pub struct HintRequestEvent;
+
+/// Sent when a tile was uncovered following a `HintRequestEvent`
+#[derive(Debug, Copy, Clone)]
+pub struct HintUsedEvent(pub Coordinates);
+
+/// Sent once the tiles of an uncover request are uncovered, `count` being more than one for a
+/// cascade
+#[derive(Debug, Copy, Clone)]
+pub struct TilesUncoveredEvent {
+    /// Tile the player uncovered
+    pub origin: Coordinates,
+    pub count: usize,
+}
+
+/// Sent when marking a tile changed it, with the new state of the tile
+#[derive(Debug, Copy, Clone)]
+pub struct TileMarkedEvent(pub Coordinates, pub TileState);
@@ -0,0 +1,235 @@
+use crate::components::Coordinates;
+use crate::raster::Canvas;
+use crate::resources::tilemap::TileMap;
+use crate::resources::{draw_tile, tile_glyphs, Board, BoardAssets, NumberStyle, TileSprite};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// What a board image shows
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub enum BoardView {
+    /// Tiles as the player sees them
+    #[default]
+    Player,
+    /// Every mine and number uncovered
+    Solution,
+}
+
+/// Look of a board image
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub view: BoardView,
+    /// Size of a tile in pixels
+    pub tile_size: u32,
+    pub style: NumberStyle,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            view: BoardView::default(),
+            tile_size: 32,
+            style: NumberStyle::default(),
+        }
+    }
+}
+
+impl ExportOptions {
+    /// Largest tile size keeping an image of a `width` x `height` board within `max_size`
+    /// pixels a side, at most the size tiles are drawn at. `None` when the board doesn't fit
+    /// even with tiles of a pixel
+    pub fn fitting_tile_size(width: u16, height: u16, max_size: u32) -> Option<u32> {
+        let tiles = width.max(height).max(1) as u32;
+        let largest = (max_size / tiles).min(crate::resources::TILE_ATLAS_CELL);
+        (1..=largest).rev().find(|tile_size| image_side(tiles, *tile_size) <= max_size)
+    }
+
+    /// Size in pixels of the image of a `width` x `height` board
+    pub fn image_size(&self, width: u16, height: u16) -> (u32, u32) {
+        let tile_size = self.tile_size.max(1);
+        (
+            image_side(width as u32, tile_size),
+            image_side(height as u32, tile_size),
+        )
+    }
+}
+
+/// Pixels across `tiles` tiles and the margins around them
+fn image_side(tiles: u32, tile_size: u32) -> u32 {
+    tiles * tile_size + 2 * (tile_size / 2)
+}
+
+/// Draws a board on the CPU and encodes it as a PNG, no window or GPU is involved. Theme
+/// textures missing from `images` are drawn as their plain color, and numbers use the
+/// built-in glyphs without `fonts` or a theme glyph sheet
+pub fn board_png(
+    board: &Board,
+    options: &ExportOptions,
+    assets: &BoardAssets,
+    images: Option<&Assets<Image>>,
+    fonts: Option<&Assets<Font>>,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let canvas = draw_board(
+        board.tile_map.width,
+        board.tile_map.height,
+        |coordinates| match options.view {
+            BoardView::Player => board.tile_sprite(coordinates),
+            BoardView::Solution => solution_sprite(&board.tile_map, coordinates, board.exploded),
+        },
+        options,
+        assets,
+        images,
+        fonts,
+    );
+    encode_png(&canvas)
+}
+
+/// Draws the solution of a tile map as `board_png` does, the view of `options` is ignored as
+/// nothing of it was played
+pub fn tile_map_png(
+    tile_map: &TileMap,
+    options: &ExportOptions,
+    assets: &BoardAssets,
+    images: Option<&Assets<Image>>,
+    fonts: Option<&Assets<Font>>,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let canvas = draw_board(
+        tile_map.width,
+        tile_map.height,
+        |coordinates| solution_sprite(tile_map, coordinates, None),
+        options,
+        assets,
+        images,
+        fonts,
+    );
+    encode_png(&canvas)
+}
+
+fn solution_sprite(
+    tile_map: &TileMap,
+    coordinates: Coordinates,
+    exploded: Option<Coordinates>,
+) -> TileSprite {
+    if exploded == Some(coordinates) {
+        TileSprite::Exploded
+    } else if tile_map.is_bomb_at(coordinates) {
+        TileSprite::Mine
+    } else {
+        TileSprite::Revealed(tile_map.displayed_count_at(coordinates))
+    }
+}
+
+/// Draws the tiles over the board background with a margin around them, the first row of the
+/// board at the bottom as on screen
+fn draw_board(
+    width: u16,
+    height: u16,
+    sprite: impl Fn(Coordinates) -> TileSprite,
+    options: &ExportOptions,
+    assets: &BoardAssets,
+    images: Option<&Assets<Image>>,
+    fonts: Option<&Assets<Font>>,
+) -> Canvas {
+    let tile_size = options.tile_size.max(1);
+    let gap = tile_size / 16;
+    let margin = tile_size / 2;
+    let (image_width, image_height) = options.image_size(width, height);
+    let mut canvas = Canvas::new(image_width, image_height);
+    canvas.fill(assets.board_material.color);
+
+    // Each look is drawn once and scaled to the tile size
+    let glyphs = tile_glyphs(assets, images, fonts);
+    let mut tiles = HashMap::default();
+    for y in 0..height {
+        for x in 0..width {
+            let sprite = sprite(Coordinates { x, y });
+            let tile = tiles.entry(sprite.index()).or_insert_with(|| {
+                draw_tile(assets, &options.style, &glyphs, sprite, images)
+                    .resized(tile_size - gap, tile_size - gap)
+            });
+            let left = margin + x as u32 * tile_size + gap / 2;
+            let top = margin + (height - 1 - y) as u32 * tile_size + gap / 2;
+            canvas.copy_from(tile, left as i32, top as i32);
+        }
+    }
+    canvas
+}
+
+fn encode_png(canvas: &Canvas) -> Result<Vec<u8>, png::EncodingError> {
+    let mut bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut bytes, canvas.width, canvas.height);
+        encoder.set_color(png::ColorType::RGBA);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&canvas.data)?;
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::Bounds2;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn board(width: u16, height: u16, bombs: u16) -> Board {
+        let mut tile_map = TileMap::empty(width, height);
+        tile_map.set_bombs(bombs, &mut ChaCha8Rng::seed_from_u64(1));
+        let bounds = Bounds2 {
+            position: Vec2::ZERO,
+            size: Vec2::new(width as f32, height as f32),
+        };
+        Board::new(tile_map, bounds, 1., 1, Entity::from_raw(0))
+    }
+
+    /// Decodes a PNG, returning its size
+    fn decoded_size(png: &[u8]) -> (u32, u32) {
+        let (info, mut reader) = png::Decoder::new(png).read_info().unwrap();
+        let mut pixels = vec![0; info.buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(info.color_type, png::ColorType::RGBA);
+        (info.width, info.height)
+    }
+
+    #[test]
+    fn board_image_has_a_tile_size_per_tile_and_margins() {
+        let board = board(9, 5, 10);
+        for view in [BoardView::Player, BoardView::Solution] {
+            let options = ExportOptions {
+                view,
+                tile_size: 16,
+                ..Default::default()
+            };
+            let png = board_png(&board, &options, &BoardAssets::plain(), None, None).unwrap();
+            assert_eq!(decoded_size(&png), (9 * 16 + 16, 5 * 16 + 16));
+        }
+    }
+
+    #[test]
+    fn tile_map_image_matches_image_size() {
+        let board = board(30, 16, 99);
+        let options = ExportOptions {
+            tile_size: 7,
+            ..Default::default()
+        };
+        let png = tile_map_png(&board.tile_map, &options, &BoardAssets::plain(), None, None).unwrap();
+        assert_eq!(decoded_size(&png), options.image_size(30, 16));
+    }
+
+    #[test]
+    fn fitting_tile_size_keeps_images_within_the_max_size() {
+        for (width, height) in [(9, 9), (30, 16), (1000, 10), (4095, 1), (4096, 4096)] {
+            let tile_size = ExportOptions::fitting_tile_size(width, height, 4096).unwrap();
+            let options = ExportOptions {
+                tile_size,
+                ..Default::default()
+            };
+            let (image_width, image_height) = options.image_size(width, height);
+            assert!(image_width <= 4096 && image_height <= 4096);
+        }
+        assert_eq!(ExportOptions::fitting_tile_size(9, 9, 4096), Some(64));
+        assert_eq!(ExportOptions::fitting_tile_size(5000, 10, 4096), None);
+    }
+}
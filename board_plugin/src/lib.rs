@@ -1,7 +1,10 @@
 mod bounds;
+mod raster;
 pub mod components;
 pub mod events;
+pub mod export;
 pub mod resources;
+pub mod solver;
 mod systems;
 
 use bevy::ecs::schedule::StateData;
@@ -9,24 +12,32 @@ use bevy::log;
 use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 
-use bevy::utils::HashMap;
+use rand::{random, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use bounds::Bounds2;
 
-use resources::BoardAssets;
-use resources::tile::Tile;
+use resources::{BoardAssets, BoardAssetsLoader, TileRenderer};
 use resources::tilemap::TileMap;
 use resources::Board;
 use resources::BoardOptions;
+use resources::NumberRule;
+use resources::NextBoardSeed;
+use resources::{TileCursor, TilePress};
+use resources::{Puzzle, PuzzlePack, PuzzlePackLoader};
 
 use crate::events::*;
-use crate::resources::BoardPosition;
-use crate::resources::TileSize;
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::RegisterInspectable;
 
 use components::*;
 
+/// Solver work spent on random boards before giving up on finding one that doesn't require
+/// guessing, counted in tiles placed and layouts enumerated. Boards are generated on the main
+/// thread, and a work budget rather than a time one keeps the board of a seed the same on every
+/// machine
+const SOLVABLE_WORK_BUDGET: usize = 1_000_000;
+
 pub struct BoardPlugin<T> {
     pub running_state: T,
 }
@@ -34,87 +45,172 @@ pub struct BoardPlugin<T> {
 impl<T: StateData> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
         app.add_system_set(
-            SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
+            SystemSet::on_enter(self.running_state.clone())
+                .with_system(Self::create_board)
+                .with_system(systems::camera::reset_camera),
         )
         .add_system_set(
             SystemSet::on_update(self.running_state.clone())
                 .with_system(systems::input::input_handling)
-                .with_system(systems::uncover::trigger_event_handler),
+                .with_system(systems::uncover::trigger_event_handler)
+                .with_system(systems::hint::hint_handler)
+                .with_system(systems::camera::zoom_camera)
+                .with_system(systems::camera::pan_camera)
+                .with_system(systems::cursor::mouse_cursor)
+                .with_system(systems::cursor::keyboard_cursor)
+                .with_system(systems::cursor::cursor_highlight)
+                .with_system(systems::feedback::tile_feedback)
+                .with_system(systems::gamepad::gamepad_cursor)
+                .with_system(systems::gamepad::gamepad_camera),
         )
         .add_system_set(
             SystemSet::on_in_stack_update(self.running_state.clone())
                 .with_system(systems::uncover::uncover_tiles)
-                .with_system(systems::mark::mark_tiles),
+                .with_system(systems::mark::mark_tiles)
+                .with_system(systems::animation::schedule_tile_tweens)
+                .with_system(systems::sprites::update_tile_sprites)
+                .with_system(systems::chunks::update_tile_chunks)
+                .with_system(systems::layout::resize_board),
+        )
+        .add_system_set(
+            SystemSet::on_pause(self.running_state.clone())
+                .with_system(systems::feedback::clear_tile_feedback),
         )
         .add_system_set(
             SystemSet::on_exit(self.running_state.clone())
                 .with_system(Self::cleanup_board),
         )
+        .init_resource::<resources::CameraControls>()
+        .init_resource::<TileCursor>()
+        .init_resource::<TilePress>()
+        .init_resource::<systems::animation::TileTweens>()
         .add_event::<TileTriggerEvent>()
         .add_event::<TileMarkEvent>()
         .add_event::<BombExplosionEvent>()
         .add_event::<BoardCompletedEvent>()
-        .add_event::<UncoverAdjacentTilesEvent>();
+        .add_event::<UncoverAdjacentTilesEvent>()
+        .add_event::<OpeningClearedEvent>()
+        .add_event::<HintRequestEvent>()
+        .add_event::<HintUsedEvent>()
+        .add_event::<TilesUncoveredEvent>()
+        .add_event::<TileMarkedEvent>()
+        .add_asset::<PuzzlePack>()
+        .init_asset_loader::<PuzzlePackLoader>()
+        .add_asset::<BoardAssets>()
+        .init_asset_loader::<BoardAssetsLoader>()
+        .add_system(systems::theme::apply_theme);
 
         log::info!("Loaded Board Plugin");
 
+        // The overlay shows the mines, it stays out of release builds even with the debug feature
+        #[cfg(all(feature = "debug", debug_assertions))]
+        app.init_resource::<resources::DebugOverlay>()
+            .add_system(systems::debug_overlay::toggle_debug_overlay)
+            .add_system(systems::debug_overlay::update_debug_tiles)
+            .add_system(systems::debug_overlay::update_debug_panel);
         #[cfg(feature = "debug")]
         {
             app.register_inspectable::<Coordinates>();
             app.register_inspectable::<BombNeighbor>();
             app.register_inspectable::<Bomb>();
-            app.register_inspectable::<Uncover>();
+            app.register_inspectable::<TileCursorHighlight>();
+            app.register_inspectable::<TileHover>();
+            app.register_inspectable::<PressedTile>();
         }
     }
 }
 
 impl<T> BoardPlugin<T> {
+    #[allow(clippy::too_many_arguments)]
     pub fn create_board(
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
         board_assets: Res<BoardAssets>,
-        window: Res<WindowDescriptor>,
+        puzzle: Option<Res<Puzzle>>,
+        next_seed: Option<Res<NextBoardSeed>>,
+        windows: Res<Windows>,
+        atlases: Res<Assets<TextureAtlas>>,
+        mut meshes: ResMut<Assets<Mesh>>,
+        mut materials: ResMut<Assets<ColorMaterial>>,
     ) {
 
-        let options = match board_options {
+        let mut options = match board_options {
             None => BoardOptions::default(),
             Some(o) => o.clone(),
         };
 
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.set_bombs(options.bomb_count);
-
-        #[cfg(feature = "debug")]
-        log::info!("{}", tile_map.console_output());
-
-        let tile_size = match options.tile_size {
-            TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => Self::adaptive_tile_size(
-                window,
-                (min, max),
-                (options.map_size.0, options.map_size.1),
-            ),
+        let (seed, seed_chosen) = match (next_seed, options.seed) {
+            (Some(next_seed), _) => {
+                commands.remove_resource::<NextBoardSeed>();
+                (next_seed.0, true)
+            }
+            (None, Some(seed)) => (seed, true),
+            (None, None) => (random(), false),
+        };
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let tile_map = match &puzzle {
+            Some(puzzle) => {
+                log::info!("Loading puzzle {}", puzzle.name);
+                options.map_size = puzzle.map_size;
+                options.safe_start = false;
+                puzzle.tile_map()
+            }
+            None => {
+                let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1)
+                    .with_number_rule(options.number_rule);
+                match options.number_rule {
+                    NumberRule::Classic => tile_map.set_bombs(options.bomb_count, &mut rng),
+                    NumberRule::Liar => {
+                        let solvable = tile_map.set_solvable_bombs(
+                            options.bomb_count,
+                            SOLVABLE_WORK_BUDGET,
+                            &mut rng,
+                        );
+                        if !solvable {
+                            log::warn!("No solvable board found, this one may require guessing");
+                        }
+                    }
+                }
+                tile_map
+            }
         };
 
-        let board_size = Vec2::new(
-            options.map_size.0 as f32 * tile_size,
-            options.map_size.1 as f32 * tile_size,
-        );
+        let window = windows.get_primary().unwrap();
+        let (tile_size, board_position, board_size) =
+            options.layout(Vec2::new(window.width(), window.height()), options.map_size);
         log::info!("board size: {}", board_size);
 
-        let board_position = match options.position {
-            BoardPosition::Centered { offset } => {
-                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
+        let board_entity = commands.spawn().id();
+        let mut board = Board::new(
+            tile_map,
+            Bounds2 {
+                position: board_position.xy(),
+                size: board_size,
+            },
+            tile_size,
+            seed,
+            board_entity,
+        );
+        board.seed_chosen = seed_chosen;
+        if let Some(puzzle) = &puzzle {
+            for coords in puzzle.flags.iter() {
+                board.try_toggle_mark(*coords);
             }
-            BoardPosition::Custom(p) => p,
-        };
-
-        let mut safe_start = None;
-        let mut covered_tiles =
-            HashMap::with_capacity((options.map_size.0 * options.map_size.1).into());
+            for coords in puzzle.revealed.iter() {
+                board.request_uncover(*coords);
+            }
+        }
+        if options.safe_start {
+            if let Some(coords) = board.tile_map.safe_start() {
+                board.request_uncover(coords);
+            }
+        }
 
-        let board_entity = commands
-            .spawn()
+        let renderer = options.renderer_for(options.map_size);
+        log::info!("Drawing tiles with {:?}", renderer);
+        let mut tile_sprites = Vec::new();
+        commands
+            .entity(board_entity)
             .insert(Name::new("Board"))
             .insert(Transform::from_translation(board_position))
             .insert(GlobalTransform::default())
@@ -130,159 +226,61 @@ impl<T> BoardPlugin<T> {
                         transform: Transform::from_xyz(board_size.x / 2., board_size.y / 2., 0.),
                         ..Default::default()
                     })
-                    .insert(Name::new("Background"));
+                    .insert(Name::new("Background"))
+                    .insert(Themed::Background);
 
-                Self::spawn_tiles(
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: board_assets.cursor_material.color,
+                            custom_size: Some(Vec2::splat(tile_size)),
+                            ..Default::default()
+                        },
+                        texture: board_assets.cursor_material.texture.clone(),
+                        visibility: Visibility { is_visible: false },
+                        transform: Transform::from_xyz(0., 0., 5.),
+                        ..Default::default()
+                    })
+                    .insert(Name::new("Tile Cursor"))
+                    .insert(Themed::Cursor)
+                    .insert(TileCursorHighlight);
+
+                systems::feedback::spawn_tile_feedback(
                     parent,
-                    &tile_map,
-                    tile_size,
+                    &board,
                     options.tile_padding,
                     &board_assets,
-                    &mut covered_tiles,
-                    &mut safe_start,
                 );
-            })
-            .id();
-
-        commands.insert_resource(Board {
-            tile_map,
-            bounds: Bounds2 {
-                position: board_position.xy(),
-                size: board_size,
-            },
-            tile_size,
-            covered_tiles,
-            marked_tiles: Vec::new(),
-            entity: board_entity
-        });
-
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
-        }
-    }
-
-    fn adaptive_tile_size(
-        window: Res<WindowDescriptor>,
-        (min, max): (f32, f32),
-        (width, height): (u16, u16),
-    ) -> f32 {
-        let max_width = window.width / width as f32;
-        let max_height = window.height / height as f32;
-        max_width.min(max_height).clamp(min, max)
-    }
-
-    fn bomb_count_text_bundle(count: u8, board_assets: &BoardAssets, size: f32) -> Text2dBundle {
-        let color = board_assets.bomb_counter_color(count);
-
-        Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: count.to_string(),
-                    style: TextStyle {
-                        font: board_assets.bomb_counter_font.clone(),
-                        font_size: size,
-                        color,
-                    },
-                }],
-                alignment: TextAlignment {
-                    vertical: VerticalAlign::Center,
-                    horizontal: HorizontalAlign::Center,
-                },
-            },
-            transform: Transform::from_xyz(0., 0., 1.),
-            ..Default::default()
-        }
-    }
-
-    fn spawn_tiles(
-        parent: &mut ChildBuilder,
-        tile_map: &TileMap,
-        size: f32,
-        padding: f32,
-        board_assets: &BoardAssets,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
-    ) {
-        for (y, line) in tile_map.iter().enumerate() {
-            for (x, tile) in line.iter().enumerate() {
-                let coordinates = Coordinates {
-                    x: x as u16,
-                    y: y as u16,
-                };
 
-                let mut cmd = parent.spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        color: board_assets.tile_material.color,
-                        custom_size: Some(Vec2::splat(size - padding)),
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(
-                        (x as f32 * size) + (size / 2.),
-                        (y as f32 * size) + (size / 2.),
-                        1.,
+                match (renderer, atlases.get(&board_assets.tile_atlas)) {
+                    (TileRenderer::Chunks, Some(atlas)) => systems::chunks::spawn_tile_chunks(
+                        parent,
+                        &board,
+                        options.tile_padding,
+                        atlas,
+                        &mut meshes,
+                        &mut materials,
                     ),
-                    texture: board_assets.tile_material.texture.clone(),
-                    ..Default::default()
-                });
-
-                cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
-                    .insert(coordinates);
-
-                cmd.with_children(|parent| {
-                    let entity = parent
-                        .spawn_bundle(SpriteBundle {
-                            sprite: Sprite {
-                                custom_size: Some(Vec2::splat(size - padding)),
-                                color: board_assets.covered_tile_material.color,
-                                ..Default::default()
-                            },
-                            texture: board_assets.covered_tile_material.texture.clone(),
-                            transform: Transform::from_xyz(0., 0., 2.),
-                            ..Default::default()
-                        })
-                        .insert(Name::new("Tile Cover"))
-                        .id();
-
-                    covered_tiles.insert(coordinates, entity);
-                    if safe_start_entity.is_none() && *tile == Tile::Empty {
-                        *safe_start_entity = Some(entity);
-                    }
-                });
-
-                match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb);
-                        cmd.with_children(|parent| {
-                            parent.spawn_bundle(SpriteBundle {
-                                sprite: Sprite {
-                                    color: board_assets.bomb_material.color,
-                                    custom_size: Some(Vec2::splat(size - padding)),
-                                    ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 1.),
-                                texture: board_assets.bomb_material.texture.clone(),
-                                ..Default::default()
-                            });
-                        });
+                    _ => {
+                        tile_sprites = systems::sprites::spawn_tile_sprites(
+                            parent,
+                            &board,
+                            options.tile_padding,
+                            &board_assets,
+                        );
                     }
-                    Tile::BombNeighbor(v) => {
-                        cmd.insert(BombNeighbor { count: *v });
-                        cmd.with_children(|parent| {
-                            parent.spawn_bundle(Self::bomb_count_text_bundle(
-                                *v,
-                                board_assets,
-                                size - padding,
-                            ));
-                        });
-                    }
-                    Tile::Empty => (),
                 }
-            }
+            });
+        if !tile_sprites.is_empty() {
+            commands.entity(board_entity).insert(TileSprites(tile_sprites));
         }
+
+        commands.insert_resource(board);
+        commands.insert_resource(TileCursor::default());
+        commands.insert_resource(TilePress::default());
+        commands.insert_resource(systems::animation::TileTweens::default());
     }
-    
+
     fn cleanup_board(board: Res<Board>, mut commands: Commands) {
         commands.entity(board.entity).despawn_recursive();
         commands.remove_resource::<Board>();
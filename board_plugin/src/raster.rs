@@ -0,0 +1,295 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// 5x7 bitmaps of the characters drawn on tiles, one string per row from the top
+const GLYPHS: [(char, [&str; 7]); 11] = [
+    ('0', [".###.", "#...#", "#..##", "#.#.#", "##..#", "#...#", ".###."]),
+    ('1', ["..#..", ".##..", "..#..", "..#..", "..#..", "..#..", ".###."]),
+    ('2', [".###.", "#...#", "....#", "...#.", "..#..", ".#...", "#####"]),
+    ('3', ["####.", "....#", "....#", ".###.", "....#", "....#", "####."]),
+    ('4', ["...#.", "..##.", ".#.#.", "#..#.", "#####", "...#.", "...#."]),
+    ('5', ["#####", "#....", "####.", "....#", "....#", "#...#", ".###."]),
+    ('6', [".###.", "#....", "#....", "####.", "#...#", "#...#", ".###."]),
+    ('7', ["#####", "....#", "...#.", "..#..", ".#...", ".#...", ".#..."]),
+    ('8', [".###.", "#...#", "#...#", ".###.", "#...#", "#...#", ".###."]),
+    ('9', [".###.", "#...#", "#...#", ".####", "....#", "....#", ".###."]),
+    ('?', [".###.", "#...#", "....#", "...#.", "..#..", ".....", "..#.."]),
+];
+
+/// RGBA pixel buffer in sRGB, drawn on the CPU with its first row at the top
+#[derive(Debug, Clone)]
+pub struct Canvas {
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+impl Canvas {
+    /// Transparent canvas
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            data: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    fn pixel_index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(((y as u32 * self.width + x as u32) * 4) as usize)
+    }
+
+    pub fn pixel(&self, x: i32, y: i32) -> [u8; 4] {
+        match self.pixel_index(x, y) {
+            Some(i) => [self.data[i], self.data[i + 1], self.data[i + 2], self.data[i + 3]],
+            None => [0; 4],
+        }
+    }
+
+    pub fn set_pixel(&mut self, x: i32, y: i32, rgba: [u8; 4]) {
+        if let Some(i) = self.pixel_index(x, y) {
+            self.data[i..i + 4].copy_from_slice(&rgba);
+        }
+    }
+
+    /// Draws `color` over a pixel, `coverage` scaling its alpha
+    pub fn blend(&mut self, x: i32, y: i32, color: Color, coverage: f32) {
+        let i = match self.pixel_index(x, y) {
+            Some(i) => i,
+            None => return,
+        };
+        let [r, g, b, a] = color.as_rgba_f32();
+        let a = a * coverage.clamp(0., 1.);
+        if a <= 0. {
+            return;
+        }
+        let dst_a = self.data[i + 3] as f32 / 255.;
+        let out_a = a + dst_a * (1. - a);
+        for (c, src) in [r, g, b].into_iter().enumerate() {
+            let dst = self.data[i + c] as f32 / 255.;
+            let out = (src * a + dst * dst_a * (1. - a)) / out_a;
+            self.data[i + c] = (out * 255.).round() as u8;
+        }
+        self.data[i + 3] = (out_a * 255.).round() as u8;
+    }
+
+    pub fn fill(&mut self, color: Color) {
+        self.fill_rect(0, 0, self.width as i32, self.height as i32, color);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: i32, height: i32, color: Color) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.blend(px, py, color, 1.);
+            }
+        }
+    }
+
+    /// Draws the pixels whose center has a negative `distance` to a shape, anti-aliased over one
+    /// pixel
+    fn fill_where(&mut self, color: Color, distance: impl Fn(f32, f32) -> f32) {
+        for py in 0..self.height as i32 {
+            for px in 0..self.width as i32 {
+                let coverage = 0.5 - distance(px as f32 + 0.5, py as f32 + 0.5);
+                if coverage > 0. {
+                    self.blend(px, py, color, coverage);
+                }
+            }
+        }
+    }
+
+    pub fn fill_circle(&mut self, center: Vec2, radius: f32, color: Color) {
+        self.fill_where(color, |x, y| Vec2::new(x, y).distance(center) - radius);
+    }
+
+    /// Circle outline, `thickness` pixels wide inside `radius`
+    pub fn ring(&mut self, center: Vec2, radius: f32, thickness: f32, color: Color) {
+        self.fill_where(color, |x, y| {
+            let distance = Vec2::new(x, y).distance(center);
+            (distance - radius).max(radius - thickness - distance)
+        });
+    }
+
+    /// Thick line between two points with round ends
+    pub fn line(&mut self, from: Vec2, to: Vec2, thickness: f32, color: Color) {
+        let segment = to - from;
+        self.fill_where(color, |x, y| {
+            let point = Vec2::new(x, y) - from;
+            let t = (point.dot(segment) / segment.length_squared()).clamp(0., 1.);
+            point.distance(segment * t) - thickness / 2.
+        });
+    }
+
+    pub fn fill_triangle(&mut self, points: [Vec2; 3], color: Color) {
+        self.fill_polygon(&points, color);
+    }
+
+    /// Fills a convex polygon, its points in either winding order
+    pub fn fill_polygon(&mut self, points: &[Vec2], color: Color) {
+        let inside = points.iter().fold(Vec2::ZERO, |sum, p| sum + *p) / points.len() as f32;
+        self.fill_where(color, |x, y| {
+            let point = Vec2::new(x, y);
+            let mut distance = f32::MIN;
+            for (i, a) in points.iter().enumerate() {
+                let b = points[(i + 1) % points.len()];
+                let edge = (b - *a).normalize();
+                let mut normal = Vec2::new(edge.y, -edge.x);
+                // Facing away from the polygon
+                if normal.dot(inside - *a) > 0. {
+                    normal = -normal;
+                }
+                distance = distance.max(normal.dot(point - *a));
+            }
+            distance
+        });
+    }
+
+    /// Draws a character of `GLYPHS` centered on the canvas, `height` pixels tall
+    pub fn draw_char(&mut self, character: char, height: f32, color: Color) {
+        let rows = match GLYPHS.iter().find(|(c, _)| *c == character) {
+            Some((_, rows)) => rows,
+            None => return,
+        };
+        let dot = (height / 7.).max(1.);
+        let origin = Vec2::new(
+            (self.width as f32 - dot * 5.) / 2.,
+            (self.height as f32 - dot * 7.) / 2.,
+        );
+        for (row, line) in rows.iter().enumerate() {
+            for (column, _) in line.chars().enumerate().filter(|(_, c)| *c == '#') {
+                let min = origin + Vec2::new(column as f32, row as f32) * dot;
+                self.fill_where(color, |x, y| {
+                    let outside = (min - Vec2::new(x, y)).max(Vec2::new(x, y) - (min + dot));
+                    outside.x.max(outside.y)
+                });
+            }
+        }
+    }
+
+    /// Draws an RGBA8 image scaled to the whole canvas, multiplied by `tint`. Images in other
+    /// formats are skipped, returning `false`
+    pub fn draw_image(&mut self, image: &Image, tint: Color) -> bool {
+        if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb {
+            return false;
+        }
+        let size = image.texture_descriptor.size;
+        let [tr, tg, tb, ta] = tint.as_rgba_f32();
+        for py in 0..self.height {
+            for px in 0..self.width {
+                let sx = (px * size.width / self.width).min(size.width - 1);
+                let sy = (py * size.height / self.height).min(size.height - 1);
+                let i = ((sy * size.width + sx) * 4) as usize;
+                let pixel = &image.data[i..i + 4];
+                let color = Color::rgba(
+                    pixel[0] as f32 / 255. * tr,
+                    pixel[1] as f32 / 255. * tg,
+                    pixel[2] as f32 / 255. * tb,
+                    pixel[3] as f32 / 255. * ta,
+                );
+                self.blend(px as i32, py as i32, color, 1.);
+            }
+        }
+        true
+    }
+
+    /// Draws the alpha of `mask` as coverage of `color`, scaled to `height` pixels and centered
+    /// on `center`. Shrunk masks average the pixels each drawn pixel covers
+    pub fn draw_mask(&mut self, mask: &Canvas, center: Vec2, height: f32, color: Color) {
+        if mask.height == 0 {
+            return;
+        }
+        let scale = height / mask.height as f32;
+        let size = Vec2::new(mask.width as f32, mask.height as f32) * scale;
+        let min = (center - size / 2.).round();
+        let footprint = (1. / scale).ceil().max(1.) as i32;
+        for py in 0..size.y.round() as i32 {
+            for px in 0..size.x.round() as i32 {
+                let sx = (px as f32 / scale) as i32;
+                let sy = (py as f32 / scale) as i32;
+                let mut alpha = 0;
+                for dy in 0..footprint {
+                    for dx in 0..footprint {
+                        alpha += mask.pixel(sx + dx, sy + dy)[3] as u32;
+                    }
+                }
+                let coverage = alpha as f32 / (255 * footprint * footprint) as f32;
+                self.blend(min.x as i32 + px, min.y as i32 + py, color, coverage);
+            }
+        }
+    }
+
+    /// Copy of an RGBA8 image, `None` for images in other formats
+    pub fn from_image(image: &Image) -> Option<Self> {
+        if image.texture_descriptor.format != TextureFormat::Rgba8UnormSrgb {
+            return None;
+        }
+        let size = image.texture_descriptor.size;
+        Some(Self {
+            width: size.width,
+            height: size.height,
+            data: image.data.clone(),
+        })
+    }
+
+    /// Part of the canvas of `width` x `height` pixels from `(x, y)`
+    pub fn crop(&self, x: i32, y: i32, width: u32, height: u32) -> Self {
+        let mut cropped = Canvas::new(width, height);
+        for py in 0..height as i32 {
+            for px in 0..width as i32 {
+                cropped.set_pixel(px, py, self.pixel(x + px, y + py));
+            }
+        }
+        cropped
+    }
+
+    /// Copy scaled to `width` x `height`, each pixel averaging the pixels it covers
+    pub fn resized(&self, width: u32, height: u32) -> Self {
+        let mut resized = Canvas::new(width, height);
+        let span = |i: u32, from: u32, to: u32| {
+            let start = (i as u64 * from as u64 / to as u64) as i32;
+            let end = ((i as u64 + 1) * from as u64 / to as u64) as i32;
+            start..end.max(start + 1)
+        };
+        for y in 0..height {
+            for x in 0..width {
+                let mut sum = [0u32; 4];
+                let mut count = 0;
+                for sy in span(y, self.height, height) {
+                    for sx in span(x, self.width, width) {
+                        let pixel = self.pixel(sx, sy);
+                        for (total, channel) in sum.iter_mut().zip(pixel) {
+                            *total += channel as u32;
+                        }
+                        count += 1;
+                    }
+                }
+                resized.set_pixel(x as i32, y as i32, sum.map(|total| (total / count) as u8));
+            }
+        }
+        resized
+    }
+
+    /// Copies `other` with its top left corner at `(x, y)`
+    pub fn copy_from(&mut self, other: &Canvas, x: i32, y: i32) {
+        for py in 0..other.height as i32 {
+            for px in 0..other.width as i32 {
+                self.set_pixel(x + px, y + py, other.pixel(px, py));
+            }
+        }
+    }
+
+    pub fn into_image(self) -> Image {
+        Image::new(
+            Extent3d {
+                width: self.width,
+                height: self.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Timing of the tile animations, in seconds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnimationOptions {
+    /// Shows every change at once, without tweens or delays
+    pub reduce_motion: bool,
+    /// Flip of a cover being uncovered
+    pub reveal: f32,
+    /// Delay before a cascading tile flips, per tile of distance from the uncovered one
+    pub cascade_delay: f32,
+    /// Flag dropping on a tile
+    pub flag: f32,
+    /// Delay before a mine goes off on a loss, per tile of distance from the uncovered one
+    pub explosion_delay: f32,
+    pub explosion: f32,
+    /// Bounce of the tiles once the board is completed
+    pub celebration: f32,
+    /// Longest delay of a cascade, explosion or celebration wave, so large boards don't take
+    /// ages to settle
+    pub max_delay: f32,
+}
+
+impl Default for AnimationOptions {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            reveal: 0.16,
+            cascade_delay: 0.03,
+            flag: 0.15,
+            explosion_delay: 0.08,
+            explosion: 0.3,
+            celebration: 0.5,
+            max_delay: 1.5,
+        }
+    }
+}
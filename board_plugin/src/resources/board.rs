@@ -1,83 +1,283 @@
 use crate::bounds::Bounds2;
+use crate::resources::TileSprite;
 use crate::{Coordinates, TileMap};
 use bevy::prelude::*;
-use bevy::utils::HashMap;
-use bevy::log;
+use bevy::utils::{HashMap, HashSet};
+
+/// What the player sees of a tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TileState {
+    Covered,
+    Flagged,
+    /// Covered with a question mark, it can still be uncovered
+    Questioned,
+    Revealed,
+}
+
+/// Tile whose look changed, drawn again by the renderer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct TileChange {
+    pub coordinates: Coordinates,
+    /// Tile the change spread from: the uncovered tile of a cascade or the mine that went off,
+    /// `coordinates` itself otherwise
+    pub origin: Coordinates,
+}
 
 #[derive(Debug)]
 pub struct Board {
     pub tile_map: TileMap,
+    /// Board area in world coordinates
     pub bounds: Bounds2,
+    /// Tile size in the board's local space, the board transform scales it to `bounds`
     pub tile_size: f32,
-    pub covered_tiles: HashMap<Coordinates, Entity>,
-    pub marked_tiles: Vec<Coordinates>,
+    /// State of every tile, row by row from the bottom
+    states: Vec<TileState>,
+    covered_count: usize,
+    flag_count: usize,
+    /// Mine the player uncovered, the game is lost once set
+    pub exploded: Option<Coordinates>,
+    /// Tiles waiting to be uncovered by `systems::uncover::uncover_tiles`
+    to_uncover: Vec<Coordinates>,
+    /// Tiles whose look changed since the renderer last drew them
+    changed: Vec<TileChange>,
+    /// Index in `TileMap::openings` of every empty tile
+    pub openings: HashMap<Coordinates, usize>,
+    pub cleared_openings: HashSet<usize>,
+    /// Seed the bomb layout was generated from
+    pub seed: u64,
+    /// Whether the seed was given rather than picked at random, by the options or a retry of
+    /// the same board, so the player may know the layout
+    pub seed_chosen: bool,
     pub entity: Entity
 }
 
 impl Board {
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
+    pub(crate) fn new(
+        tile_map: TileMap,
+        bounds: Bounds2,
+        tile_size: f32,
+        seed: u64,
+        entity: Entity,
+    ) -> Self {
+        let openings = tile_map
+            .openings()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, opening)| opening.into_iter().map(move |c| (c, i)))
+            .collect();
+        let tile_count = tile_map.width as usize * tile_map.height as usize;
+        Self {
+            tile_map,
+            bounds,
+            tile_size,
+            states: vec![TileState::Covered; tile_count],
+            covered_count: tile_count,
+            flag_count: 0,
+            exploded: None,
+            to_uncover: Vec::new(),
+            changed: Vec::new(),
+            openings,
+            cleared_openings: HashSet::default(),
+            seed,
+            seed_chosen: false,
+            entity,
+        }
+    }
+
+    /// Tile under a window position, going through the transform of the camera showing the board
+    pub fn mouse_position(
+        &self,
+        window: &Window,
+        camera: &GlobalTransform,
+        position: Vec2,
+    ) -> Option<Coordinates> {
         let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
+        let position = camera
+            .mul_vec3((position - window_size / 2.).extend(0.))
+            .truncate();
 
         if !self.bounds.in_bounds(position) {
             return None;
         }
 
         let coordinates = position - self.bounds.position;
+        let tile_size = self.bounds.size.x / self.tile_map.width as f32;
         Some(Coordinates {
-            x: (coordinates.x / self.tile_size) as u16,
-            y: (coordinates.y / self.tile_size) as u16,
+            x: ((coordinates.x / tile_size) as u16).min(self.tile_map.width - 1),
+            y: ((coordinates.y / tile_size) as u16).min(self.tile_map.height - 1),
         })
     }
 
-    pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        if self.marked_tiles.contains(coords) {
-            None
-        } else {
-            self.covered_tiles.get(coords)
+    /// Index of a tile in `states`, `None` off the board
+    fn index(&self, coords: Coordinates) -> Option<usize> {
+        if coords.x >= self.tile_map.width || coords.y >= self.tile_map.height {
+            return None;
         }
+        Some(coords.y as usize * self.tile_map.width as usize + coords.x as usize)
+    }
+
+    /// State of a tile, `None` off the board
+    pub fn state(&self, coords: Coordinates) -> Option<TileState> {
+        self.index(coords).map(|index| self.states[index])
     }
 
-    pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
-        if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
+    fn set_state(&mut self, coords: Coordinates, state: TileState, origin: Coordinates) {
+        let index = match self.index(coords) {
+            Some(index) => index,
+            None => return,
+        };
+        let previous = std::mem::replace(&mut self.states[index], state);
+        if previous == TileState::Flagged {
+            self.flag_count -= 1;
+        }
+        match state {
+            TileState::Flagged => self.flag_count += 1,
+            TileState::Revealed => self.covered_count -= 1,
+            _ => (),
         }
-        self.covered_tiles.remove(coords)
+        self.changed.push(TileChange {
+            coordinates: coords,
+            origin,
+        });
+    }
+
+    /// Whether a tile of the board is covered, `false` off the board
+    pub fn is_covered(&self, coords: Coordinates) -> bool {
+        matches!(self.state(coords), Some(state) if state != TileState::Revealed)
+    }
+
+    /// Whether the player can uncover a tile, flagged tiles are protected
+    pub fn can_uncover(&self, coords: Coordinates) -> bool {
+        matches!(
+            self.state(coords),
+            Some(TileState::Covered | TileState::Questioned)
+        )
+    }
+
+    pub fn flag_count(&self) -> usize {
+        self.flag_count
+    }
+
+    /// Tiles not uncovered yet, flagged ones included
+    pub fn covered_count(&self) -> usize {
+        self.covered_count
+    }
+
+    pub fn flagged_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        self.coordinates()
+            .filter(|c| self.state(*c) == Some(TileState::Flagged))
+    }
+
+    /// Every coordinates of the board, row by row from the bottom
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinates> {
+        let width = self.tile_map.width;
+        (0..self.tile_map.height).flat_map(move |y| (0..width).map(move |x| Coordinates { x, y }))
     }
 
-    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
-        let entity = *self.covered_tiles.get(coords)?;
-        let mark = if self.marked_tiles.contains(coords) {
-            self.unmark_tile(coords)?;
-            false
-        } else {
-            self.marked_tiles.push(*coords);
-            true
+    /// Queues a tile to be uncovered when the player can uncover it, returning whether it was
+    pub fn request_uncover(&mut self, coords: Coordinates) -> bool {
+        let requested = self.can_uncover(coords);
+        if requested {
+            self.to_uncover.push(coords);
+        }
+        requested
+    }
+
+    pub(crate) fn take_uncover_requests(&mut self) -> Vec<Coordinates> {
+        std::mem::take(&mut self.to_uncover)
+    }
+
+    /// Uncovers a covered tile, flagged or not, returning `false` if it already was. `origin`
+    /// is the tile the player uncovered when `coords` is part of its cascade
+    pub fn try_uncover_tile(&mut self, coords: Coordinates, origin: Coordinates) -> bool {
+        if !self.is_covered(coords) {
+            return false;
+        }
+        self.set_state(coords, TileState::Revealed, origin);
+        true
+    }
+
+    /// Flags or unflags a covered tile, returning whether it is now flagged
+    pub fn try_toggle_mark(&mut self, coords: Coordinates) -> Option<bool> {
+        let state = match self.state(coords)? {
+            TileState::Flagged => TileState::Covered,
+            TileState::Covered | TileState::Questioned => TileState::Flagged,
+            TileState::Revealed => return None,
+        };
+        self.set_state(coords, state, coords);
+        Some(state == TileState::Flagged)
+    }
+
+    /// Turns the flag of a tile into a question mark or removes the question mark, returning
+    /// whether the tile now shows one
+    pub fn try_toggle_question(&mut self, coords: Coordinates) -> Option<bool> {
+        let state = match self.state(coords)? {
+            TileState::Questioned => TileState::Covered,
+            TileState::Covered | TileState::Flagged => TileState::Questioned,
+            TileState::Revealed => return None,
         };
-        Some((entity, mark))
+        self.set_state(coords, state, coords);
+        Some(state == TileState::Questioned)
     }
 
-    pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
+    /// Neighbors of a tile that are on the board
+    pub fn neighbors(&self, coord: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
         self.tile_map
             .safe_square_at(coord)
-            .filter_map(|c| self.covered_tiles.get(&c))
-            .copied()
-            .collect()
-    }
-
-    fn unmark_tile(&mut self, coords: &Coordinates) -> Option<Coordinates> {
-        let pos = match self.marked_tiles.iter().position(|a| a == coords) {
-            None => {
-                log::error!("Failed to unmark tile at {}", coords);
-                return None;
-            },
-            Some(p) => p,
-        };
-        Some(self.marked_tiles.remove(pos))
+            .filter(|c| c.x < self.tile_map.width && c.y < self.tile_map.height)
+    }
+
+    /// Covered neighbors of a tile, flagged ones included
+    pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Coordinates> {
+        self.neighbors(coord).filter(|c| self.is_covered(*c)).collect()
+    }
+
+    /// Records the uncovered mine that lost the game, every mine and wrong flag then shows
+    pub fn explode(&mut self, coords: Coordinates) {
+        self.exploded = Some(coords);
+        let shown: Vec<_> = self
+            .coordinates()
+            .filter(|c| {
+                self.tile_map.is_bomb_at(*c) || self.state(*c) == Some(TileState::Flagged)
+            })
+            .map(|c| TileChange {
+                coordinates: c,
+                origin: coords,
+            })
+            .collect();
+        self.changed.extend(shown);
+    }
+
+    /// Records the opening of an uncovered empty tile as cleared, returning `true` the first
+    /// time it happens
+    pub fn clear_opening(&mut self, coords: &Coordinates) -> bool {
+        match self.openings.get(coords) {
+            Some(opening) => self.cleared_openings.insert(*opening),
+            None => false,
+        }
     }
 
     pub fn is_completed(&self) -> bool {
-        self.tile_map.bomb_count as usize == self.covered_tiles.len()
+        self.tile_map.bomb_count as usize == self.covered_count
     }
 
+    /// Atlas cell a tile is drawn with, tiles off the board are drawn covered
+    pub fn tile_sprite(&self, coords: Coordinates) -> TileSprite {
+        let bomb = self.tile_map.is_bomb_at(coords);
+        let lost = self.exploded.is_some();
+        match self.state(coords).unwrap_or(TileState::Covered) {
+            TileState::Revealed if bomb => TileSprite::Exploded,
+            TileState::Revealed => TileSprite::Revealed(self.tile_map.displayed_count_at(coords)),
+            TileState::Flagged if lost && !bomb => TileSprite::WrongFlag,
+            TileState::Flagged => TileSprite::Flag,
+            _ if lost && bomb => TileSprite::Mine,
+            TileState::Questioned => TileSprite::Question,
+            TileState::Covered => TileSprite::Covered,
+        }
+    }
+
+    /// Tiles whose look changed since the last call
+    pub(crate) fn take_changed(&mut self) -> Vec<TileChange> {
+        std::mem::take(&mut self.changed)
+    }
 }
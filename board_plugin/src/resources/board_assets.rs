@@ -1,5 +1,12 @@
+use crate::components::Themed;
+use crate::resources::{GlyphSheetTexture, Palette, SoundEffect, TILE_CHARACTERS};
+use bevy::asset::{AssetLoader, AssetPath, LoadContext, LoadedAsset};
+use bevy::log;
 use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
 use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::utils::{BoxedFuture, HashMap};
+use serde::Deserialize;
 
 #[derive(Debug, Clone)]
 pub struct SpriteMaterial {
@@ -16,27 +23,69 @@ impl Default for SpriteMaterial {
     }
 }
 
-#[derive(Debug, Clone)]
+/// Board theme, loaded from a `.theme.ron` file and inserted as a resource once active
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "b3c1f0d2-6e5a-4f8b-9c27-1d4e8a7f3b60"]
 pub struct BoardAssets {
+    /// Theme name shown to the player
     pub label: String,
     pub board_material: SpriteMaterial,
     pub tile_material: SpriteMaterial,
     pub covered_tile_material: SpriteMaterial,
     pub bomb_counter_font: Handle<Font>,
     pub bomb_counter_colors: Vec<Color>,
+    /// Prebaked number glyphs taking over the font on tiles
+    pub glyph_sheet: Option<GlyphSheetTexture>,
     pub flag_material: SpriteMaterial,
     pub bomb_material: SpriteMaterial,
+    /// Overlay of the tile under the keyboard cursor, keep it translucent
+    pub cursor_material: SpriteMaterial,
+    /// Every tile look drawn from the materials above, indexed by `TileSprite::index`. Built
+    /// once the theme textures are loaded
+    pub tile_atlas: Handle<TextureAtlas>,
+    /// Asset path of the sound of every effect the theme plays, effects left out are silent.
+    /// The sounds are loaded by whoever plays them
+    pub sounds: HashMap<SoundEffect, String>,
+}
+
+#[cfg(test)]
+impl BoardAssets {
+    /// Theme of plain colors without any file, for tests
+    pub(crate) fn plain() -> Self {
+        Self {
+            label: "Test".to_string(),
+            board_material: SpriteMaterial {
+                color: Color::GRAY,
+                ..Default::default()
+            },
+            tile_material: Default::default(),
+            covered_tile_material: SpriteMaterial {
+                color: Color::DARK_GRAY,
+                ..Default::default()
+            },
+            bomb_counter_font: Default::default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            glyph_sheet: None,
+            flag_material: Default::default(),
+            bomb_material: Default::default(),
+            cursor_material: Default::default(),
+            tile_atlas: Default::default(),
+            sounds: Default::default(),
+        }
+    }
 }
 
 impl BoardAssets {
+    /// Number colors of themes that don't pick theirs
     pub fn default_colors() -> Vec<Color> {
-        vec![
-            Color::WHITE,
-            Color::GREEN,
-            Color::YELLOW,
-            Color::ORANGE,
-            Color::PURPLE,
-        ]
+        Palette::Classic.colors().to_vec()
+    }
+
+    pub fn material(&self, part: Themed) -> &SpriteMaterial {
+        match part {
+            Themed::Background => &self.board_material,
+            Themed::Cursor => &self.cursor_material,
+        }
     }
 
     pub fn bomb_counter_color(&self, counter: u8) -> Color {
@@ -50,3 +99,127 @@ impl BoardAssets {
         }
     }
 }
+
+/// Theme handle the board is drawn with, its `BoardAssets` resource follows the asset as it loads
+/// and reloads
+#[derive(Debug, Clone)]
+pub struct ActiveTheme(pub Handle<BoardAssets>);
+
+/// Sprite material of a theme file, the texture path is relative to the assets folder
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct MaterialDescription {
+    color: Color,
+    texture: Option<String>,
+}
+
+impl Default for MaterialDescription {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            texture: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ThemeDescription {
+    label: String,
+    font: String,
+    board: MaterialDescription,
+    tile: MaterialDescription,
+    covered_tile: MaterialDescription,
+    flag: MaterialDescription,
+    bomb: MaterialDescription,
+    cursor: MaterialDescription,
+    #[serde(default = "BoardAssets::default_colors")]
+    number_colors: Vec<Color>,
+    /// Sound file of each effect, relative to the assets folder
+    #[serde(default)]
+    sounds: HashMap<SoundEffect, String>,
+    #[serde(default)]
+    glyph_sheet: Option<GlyphSheetDescription>,
+}
+
+/// Glyph sheet of a theme file, a horizontal strip of cells of the same width
+#[derive(Debug, Deserialize)]
+struct GlyphSheetDescription {
+    texture: String,
+    /// Character of each cell from the left
+    #[serde(default = "default_sheet_characters")]
+    characters: String,
+}
+
+fn default_sheet_characters() -> String {
+    TILE_CHARACTERS.to_string()
+}
+
+/// Loads `.theme.ron` files, along with the font and textures they point to
+#[derive(Default)]
+pub struct BoardAssetsLoader;
+
+impl AssetLoader for BoardAssetsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let theme: ThemeDescription = ron::de::from_bytes(bytes)?;
+            let mut dependencies = Vec::new();
+            let mut material = |description: MaterialDescription| SpriteMaterial {
+                color: description.color,
+                texture: match description.texture {
+                    Some(path) => {
+                        let path = AssetPath::new(path.into(), None);
+                        let handle = load_context.get_handle(path.get_id());
+                        dependencies.push(path);
+                        handle
+                    }
+                    None => DEFAULT_IMAGE_HANDLE.typed(),
+                },
+            };
+            let board_material = material(theme.board);
+            let tile_material = material(theme.tile);
+            let covered_tile_material = material(theme.covered_tile);
+            let flag_material = material(theme.flag);
+            let bomb_material = material(theme.bomb);
+            let cursor_material = material(theme.cursor);
+
+            let glyph_sheet = theme.glyph_sheet.map(|sheet| {
+                let path = AssetPath::new(sheet.texture.into(), None);
+                let texture = load_context.get_handle(path.get_id());
+                dependencies.push(path);
+                GlyphSheetTexture {
+                    texture,
+                    characters: sheet.characters,
+                }
+            });
+
+            let font_path = AssetPath::new(theme.font.into(), None);
+            let font = load_context.get_handle(font_path.get_id());
+            dependencies.push(font_path);
+            let assets = BoardAssets {
+                label: theme.label,
+                board_material,
+                tile_material,
+                covered_tile_material,
+                bomb_counter_font: font,
+                bomb_counter_colors: theme.number_colors,
+                glyph_sheet,
+                flag_material,
+                bomb_material,
+                cursor_material,
+                tile_atlas: Default::default(),
+                sounds: theme.sounds,
+            };
+            log::info!("Loaded theme {}", assets.label);
+            load_context.set_default_asset(LoadedAsset::new(assets).with_dependencies(dependencies));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["theme.ron"]
+    }
+}
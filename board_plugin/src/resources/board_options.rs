@@ -1,4 +1,5 @@
-use bevy::prelude::Vec3;
+use crate::resources::NumberRule;
+use bevy::prelude::{Vec2, Vec3};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,16 +14,48 @@ pub enum BoardPosition {
     Custom(Vec3),
 }
 
+/// How the tiles are drawn
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TileRenderer {
+    /// One sprite entity per tile
+    Sprites,
+    /// A few meshes of `CHUNK_SIZE` x `CHUNK_SIZE` tiles, for boards too large for an entity per tile
+    Chunks,
+    /// Chunks for boards of more than `CHUNKED_TILE_COUNT` tiles, sprites otherwise
+    Auto,
+}
+
+/// Tile count above which `TileRenderer::Auto` draws chunks, the largest custom boards of the
+/// client are past it
+pub const CHUNKED_TILE_COUNT: usize = 64 * 64;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BoardOptions {
     pub map_size: (u16, u16),
     pub bomb_count: u16,
     pub position: BoardPosition,
+    /// Window height kept free above the board, for a HUD
+    pub top_margin: f32,
     pub tile_size: TileSize,
     pub tile_padding: f32,
+    pub renderer: TileRenderer,
     pub safe_start: bool,
+    /// Middle click uncovers the tiles around a number
+    pub chording: bool,
+    /// Marking a flagged tile turns the flag into a question mark
+    pub question_marks: bool,
+    pub number_rule: NumberRule,
+    /// Seed of the bomb layout, a random one is picked when not set. Boards are generated with
+    /// `ChaCha8Rng`, so a seed gives the same board on every platform and release
+    pub seed: Option<u64>,
 }
 
+/// Seed of the next board only, taking over `BoardOptions::seed`. Removed once the board
+/// is created, used to replay a board.
+#[derive(Debug, Copy, Clone)]
+pub struct NextBoardSeed(pub u64);
+
 impl Default for TileSize {
     fn default() -> Self {
         Self::Adaptive { min: 10.0, max: 50.0 }
@@ -41,9 +74,58 @@ impl Default for BoardOptions {
             map_size: (15, 15),
             bomb_count: 30,
             position: Default::default(),
+            top_margin: 0.,
             tile_size: Default::default(),
             tile_padding: 0.,
+            renderer: TileRenderer::Auto,
             safe_start: false,
+            chording: true,
+            question_marks: false,
+            number_rule: Default::default(),
+            seed: None,
+        }
+    }
+}
+
+impl BoardOptions {
+    /// Renderer of a board of `map_size` tiles, `TileRenderer::Auto` resolved
+    pub fn renderer_for(&self, map_size: (u16, u16)) -> TileRenderer {
+        match self.renderer {
+            TileRenderer::Auto if map_size.0 as usize * map_size.1 as usize > CHUNKED_TILE_COUNT => {
+                TileRenderer::Chunks
+            }
+            TileRenderer::Auto => TileRenderer::Sprites,
+            renderer => renderer,
         }
     }
+
+    /// Tile size, position and size of a board of `map_size` tiles in a window of
+    /// `window_size`
+    pub fn layout(&self, window_size: Vec2, map_size: (u16, u16)) -> (f32, Vec3, Vec2) {
+        let tile_size = match self.tile_size {
+            TileSize::Fixed(v) => v,
+            TileSize::Adaptive { min, max } => {
+                let max_width = window_size.x / map_size.0 as f32;
+                let max_height = (window_size.y - self.top_margin) / map_size.1 as f32;
+                max_width.min(max_height).clamp(min, max)
+            }
+        };
+
+        let board_size = Vec2::new(
+            map_size.0 as f32 * tile_size,
+            map_size.1 as f32 * tile_size,
+        );
+
+        let board_position = match self.position {
+            BoardPosition::Centered { offset } => {
+                Vec3::new(
+                    -(board_size.x / 2.),
+                    -(board_size.y / 2.) - self.top_margin / 2.,
+                    0.,
+                ) + offset
+            }
+            BoardPosition::Custom(p) => p,
+        };
+        (tile_size, board_position, board_size)
+    }
 }
@@ -0,0 +1,48 @@
+use bevy::prelude::{KeyCode, MouseButton};
+use serde::{Deserialize, Serialize};
+
+/// How the player moves the board camera
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CameraControls {
+    /// Mouse button dragging the board, its clicks no longer reach the tiles
+    pub pan_button: Option<MouseButton>,
+    /// Holding this key turns left drags into pans
+    pub pan_key: KeyCode,
+    /// Holding this key makes the arrow keys pan the board
+    pub keyboard_pan_modifier: KeyCode,
+    /// Keyboard pan speed in window pixels per second
+    pub keyboard_pan_speed: f32,
+    /// Zoom change of a mouse wheel step
+    pub zoom_step: f32,
+    /// Closest zoom, as a camera scale
+    pub min_zoom: f32,
+    /// Farthest zoom, as a camera scale
+    pub max_zoom: f32,
+}
+
+impl Default for CameraControls {
+    fn default() -> Self {
+        Self {
+            pan_button: None,
+            pan_key: KeyCode::Space,
+            keyboard_pan_modifier: KeyCode::LShift,
+            keyboard_pan_speed: 600.,
+            zoom_step: 0.1,
+            min_zoom: 0.2,
+            max_zoom: 4.,
+        }
+    }
+}
+
+impl CameraControls {
+    /// Whether a press of `button` pans the board instead of reaching the tiles
+    pub fn pans_with(&self, button: MouseButton, pan_key_pressed: bool) -> bool {
+        self.pan_button == Some(button) || (button == MouseButton::Left && pan_key_pressed)
+    }
+
+    /// Whether the camera reads `key`, space also reveals the tile under the cursor
+    pub fn uses_key(&self, key: KeyCode) -> bool {
+        key == self.pan_key || key == self.keyboard_pan_modifier
+    }
+}
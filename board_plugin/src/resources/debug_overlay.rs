@@ -0,0 +1,27 @@
+use crate::components::Coordinates;
+use crate::solver::Deduction;
+use bevy::prelude::KeyCode;
+use bevy::utils::HashMap;
+
+/// In-game debug overlay: ghosts of the mines, what the solver knows of the covered tiles and a
+/// panel of the board state
+#[derive(Debug, Clone)]
+pub struct DebugOverlay {
+    pub visible: bool,
+    /// Shows or hides the overlay
+    pub toggle_key: KeyCode,
+    /// Chance of every covered tile to hold a bomb, computed again as the board changes
+    pub(crate) probabilities: HashMap<Coordinates, f32>,
+    pub(crate) deductions: HashMap<Coordinates, Deduction>,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            toggle_key: KeyCode::F3,
+            probabilities: HashMap::default(),
+            deductions: HashMap::default(),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use crate::raster::Canvas;
+use crate::resources::BoardAssets;
+use ab_glyph::{Font as _, FontArc};
+use bevy::log;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Characters drawn on tiles
+pub const TILE_CHARACTERS: &str = "12345678?";
+
+/// Glyph sheet texture of a theme: white characters on a transparent background, in cells of
+/// the same width from left to right
+#[derive(Debug, Clone)]
+pub struct GlyphSheetTexture {
+    pub texture: Handle<Image>,
+    /// Character of each cell, in order
+    pub characters: String,
+}
+
+/// Alpha masks of the characters drawn on tiles, baked once per tile atlas
+#[derive(Debug, Clone)]
+pub(crate) struct GlyphSheet {
+    glyphs: HashMap<char, Canvas>,
+}
+
+impl GlyphSheet {
+    /// Glyphs of the theme sheet, else rasterized from the theme font at `height` pixels, else
+    /// the built-in bitmaps while the font isn't loaded, failed to or there are no fonts
+    pub fn for_theme(
+        assets: &BoardAssets,
+        height: u32,
+        images: Option<&Assets<Image>>,
+        fonts: Option<&Assets<Font>>,
+    ) -> Self {
+        let sheet = assets.glyph_sheet.as_ref().and_then(|sheet| {
+            let image = images?.get(&sheet.texture)?;
+            let glyphs = Self::from_sheet(image, &sheet.characters);
+            if glyphs.is_none() {
+                log::warn!("Glyph sheet of theme {} isn't RGBA8, using its font", assets.label);
+            }
+            glyphs
+        });
+        sheet
+            .or_else(|| {
+                fonts?
+                    .get(&assets.bomb_counter_font)
+                    .map(|font| Self::from_font(&font.font, height))
+            })
+            .unwrap_or_else(|| Self::bitmap(height))
+    }
+
+    /// Rasterizes the tile characters, scaled together so the tallest is `height` pixels
+    pub fn from_font(font: &FontArc, height: u32) -> Self {
+        const PROBE_SCALE: f32 = 100.;
+        let tallest = TILE_CHARACTERS
+            .chars()
+            .filter_map(|c| font.outline_glyph(font.glyph_id(c).with_scale(PROBE_SCALE)))
+            .map(|glyph| glyph.px_bounds().height())
+            .fold(0., f32::max);
+        if tallest <= 0. {
+            return Self::bitmap(height);
+        }
+        let scale = PROBE_SCALE * height as f32 / tallest;
+        let glyphs = TILE_CHARACTERS
+            .chars()
+            .filter_map(|c| {
+                let glyph = font.outline_glyph(font.glyph_id(c).with_scale(scale))?;
+                let bounds = glyph.px_bounds();
+                let mut mask = Canvas::new(bounds.width() as u32, bounds.height() as u32);
+                glyph.draw(|x, y, coverage| {
+                    mask.blend(x as i32, y as i32, Color::WHITE, coverage);
+                });
+                Some((c, mask))
+            })
+            .collect();
+        Self { glyphs }
+    }
+
+    /// Cuts a sheet image into one glyph per character, `None` if it isn't RGBA8. Rows left empty
+    /// by every cell are trimmed so the glyphs fill the height they are drawn at
+    pub fn from_sheet(image: &Image, characters: &str) -> Option<Self> {
+        let sheet = Canvas::from_image(image)?;
+        let count = characters.chars().count().max(1) as u32;
+        let width = sheet.width / count;
+        let inked = |y: u32| {
+            Iterator::any(&mut (0..sheet.width as i32), |x| sheet.pixel(x, y as i32)[3] > 0)
+        };
+        let top = (0..sheet.height).find(|y| inked(*y)).unwrap_or(0);
+        let bottom = (0..sheet.height).rfind(|y| inked(*y)).map_or(sheet.height, |y| y + 1);
+        let glyphs = characters
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let x = (i as u32 * width) as i32;
+                (c, sheet.crop(x, top as i32, width, bottom.saturating_sub(top)))
+            })
+            .collect();
+        Some(Self { glyphs })
+    }
+
+    /// Built-in 5x7 bitmaps
+    pub fn bitmap(height: u32) -> Self {
+        let glyphs = TILE_CHARACTERS
+            .chars()
+            .map(|c| {
+                let mut mask = Canvas::new(height * 5 / 7, height);
+                mask.draw_char(c, height as f32, Color::WHITE);
+                (c, mask)
+            })
+            .collect();
+        Self { glyphs }
+    }
+
+    /// Draws a character centered on the canvas, `height` pixels tall
+    pub fn draw(&self, canvas: &mut Canvas, character: char, height: f32, color: Color) {
+        if let Some(mask) = self.glyphs.get(&character) {
+            let center = Vec2::new(canvas.width as f32, canvas.height as f32) / 2.;
+            canvas.draw_mask(mask, center, height, color);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+/// Board input from the mouse is ignored while this resource exists, as when a replay
+/// sends the events instead
+#[derive(Debug, Copy, Clone, Default)]
+pub struct InputLock;
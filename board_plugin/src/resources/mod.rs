@@ -4,7 +4,33 @@ pub(crate) mod tilemap;
 mod board;
 mod board_options;
 mod board_assets;
+mod number_rule;
+mod puzzle;
+mod input_lock;
+mod camera_controls;
+mod tile_cursor;
+mod tile_atlas;
+mod animation_options;
+mod tile_press;
+mod palette;
+mod sound;
+mod glyph_sheet;
+#[cfg(all(feature = "debug", debug_assertions))]
+mod debug_overlay;
 
 pub use board::*;
 pub use board_options::*;
 pub use board_assets::*;
+pub use number_rule::*;
+pub use puzzle::*;
+pub use input_lock::*;
+pub use camera_controls::*;
+pub use tile_cursor::*;
+pub use tile_atlas::*;
+pub use animation_options::*;
+pub use tile_press::*;
+pub use palette::*;
+pub use sound::*;
+pub use glyph_sheet::*;
+#[cfg(all(feature = "debug", debug_assertions))]
+pub use debug_overlay::*;
@@ -0,0 +1,89 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Decides which number is displayed on a bomb neighbor tile from its actual bomb count
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum NumberRule {
+    /// The actual count is displayed
+    #[default]
+    Classic,
+    /// The displayed count is off by exactly one, above or below
+    Liar,
+}
+
+impl NumberRule {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NumberRule::Classic => "Classic",
+            NumberRule::Liar => "Liar",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            NumberRule::Classic => NumberRule::Liar,
+            NumberRule::Liar => NumberRule::Classic,
+        }
+    }
+
+    pub fn displayed_count<R: Rng>(&self, count: u8, rng: &mut R) -> u8 {
+        match self {
+            NumberRule::Classic => count,
+            NumberRule::Liar => match count {
+                0 => 0,
+                8 => 7,
+                _ if rng.gen_bool(0.5) => count - 1,
+                _ => count + 1,
+            },
+        }
+    }
+
+    /// Bit mask of the actual counts a bomb neighbor tile displaying `displayed` can have
+    pub fn possible_counts(&self, displayed: u8) -> u16 {
+        match self {
+            NumberRule::Classic => 1 << displayed,
+            NumberRule::Liar => {
+                let below = if displayed > 1 { 1 << (displayed - 1) } else { 0 };
+                let above = if displayed < 8 { 1 << (displayed + 1) } else { 0 };
+                below | above
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn classic_counts_are_the_displayed_one() {
+        for count in 0..=8 {
+            assert_eq!(NumberRule::Classic.possible_counts(count), 1 << count);
+        }
+    }
+
+    #[test]
+    fn liar_counts_are_one_off_the_displayed_one() {
+        assert_eq!(NumberRule::Liar.possible_counts(0), 0b10);
+        assert_eq!(NumberRule::Liar.possible_counts(1), 0b100);
+        assert_eq!(NumberRule::Liar.possible_counts(3), 0b1_0100);
+        assert_eq!(NumberRule::Liar.possible_counts(7), 0b1_0100_0000);
+        assert_eq!(NumberRule::Liar.possible_counts(8), 0b1000_0000);
+    }
+
+    #[test]
+    fn displayed_counts_allow_the_actual_one() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        for _ in 0..32 {
+            for count in 1..=8 {
+                let liar = NumberRule::Liar.displayed_count(count, &mut rng);
+                assert_ne!(liar, count);
+                assert_ne!(NumberRule::Liar.possible_counts(liar) & (1 << count), 0);
+                let classic = NumberRule::Classic.displayed_count(count, &mut rng);
+                assert_ne!(NumberRule::Classic.possible_counts(classic) & (1 << count), 0);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use crate::resources::BoardAssets;
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+/// Colors of the numbers 1 to 8, picked to stay readable on dark revealed tiles
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Palette {
+    #[default]
+    Classic,
+    /// Avoids telling numbers apart by red and green, for deuteranopia
+    Deuteranopia,
+    /// Avoids dark reds, which look black with protanopia
+    Protanopia,
+    /// Avoids telling numbers apart by blue and yellow, for tritanopia
+    Tritanopia,
+    /// Saturated colors far apart in brightness
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 5] = [
+        Palette::Classic,
+        Palette::Deuteranopia,
+        Palette::Protanopia,
+        Palette::Tritanopia,
+        Palette::HighContrast,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Palette::Classic => "Classic",
+            Palette::Deuteranopia => "Deuteranopia",
+            Palette::Protanopia => "Protanopia",
+            Palette::Tritanopia => "Tritanopia",
+            Palette::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|p| p == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Color of every number, from 1 to 8
+    pub fn colors(&self) -> [Color; 8] {
+        match self {
+            Palette::Classic => [
+                Color::rgb(0.4, 0.6, 1.),
+                Color::rgb(0.3, 0.8, 0.3),
+                Color::rgb(1., 0.35, 0.35),
+                Color::rgb(0.7, 0.45, 1.),
+                Color::rgb(1., 0.6, 0.2),
+                Color::rgb(0.2, 0.85, 0.85),
+                Color::rgb(0.95, 0.95, 0.95),
+                Color::rgb(0.65, 0.65, 0.65),
+            ],
+            // Okabe-Ito colors
+            Palette::Deuteranopia => [
+                Color::rgb(0.34, 0.71, 0.91),
+                Color::rgb(0.9, 0.62, 0.),
+                Color::rgb(0.94, 0.89, 0.26),
+                Color::rgb(0.6, 0.6, 1.),
+                Color::rgb(0.8, 0.47, 0.65),
+                Color::rgb(0., 0.62, 0.45),
+                Color::rgb(1., 1., 1.),
+                Color::rgb(0.6, 0.6, 0.6),
+            ],
+            Palette::Protanopia => [
+                Color::rgb(0.34, 0.71, 0.91),
+                Color::rgb(0.94, 0.89, 0.26),
+                Color::rgb(1., 0.75, 0.45),
+                Color::rgb(0.55, 0.55, 1.),
+                Color::rgb(0.85, 0.6, 0.8),
+                Color::rgb(0.4, 0.9, 0.9),
+                Color::rgb(1., 1., 1.),
+                Color::rgb(0.6, 0.6, 0.6),
+            ],
+            Palette::Tritanopia => [
+                Color::rgb(0.3, 0.85, 0.85),
+                Color::rgb(1., 0.4, 0.4),
+                Color::rgb(0.95, 0.5, 0.85),
+                Color::rgb(0.1, 0.6, 0.6),
+                Color::rgb(0.75, 0.2, 0.2),
+                Color::rgb(0.95, 0.8, 0.85),
+                Color::rgb(1., 1., 1.),
+                Color::rgb(0.6, 0.6, 0.6),
+            ],
+            Palette::HighContrast => [
+                Color::rgb(0., 1., 1.),
+                Color::rgb(0., 1., 0.),
+                Color::rgb(1., 0.3, 0.3),
+                Color::rgb(1., 1., 0.),
+                Color::rgb(1., 0.5, 0.),
+                Color::rgb(1., 0.3, 1.),
+                Color::rgb(1., 1., 1.),
+                Color::rgb(0.7, 0.7, 1.),
+            ],
+        }
+    }
+}
+
+/// Mark drawn next to the numbers so their color is never the only way to tell them apart
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum NumberCue {
+    #[default]
+    None,
+    /// As many dots as the number along the bottom of the tile
+    Pips,
+    /// Shape in the corner: dot, bar, triangle, square, diamond, hexagon, ring and cross from 1
+    /// to 8
+    Shapes,
+}
+
+impl NumberCue {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NumberCue::None => "None",
+            NumberCue::Pips => "Pips",
+            NumberCue::Shapes => "Shapes",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            NumberCue::None => NumberCue::Pips,
+            NumberCue::Pips => NumberCue::Shapes,
+            NumberCue::Shapes => NumberCue::None,
+        }
+    }
+}
+
+/// How numbers are drawn in the tile atlas, it is built again when this resource changes
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumberStyle {
+    /// Palette taking over the number colors of the theme
+    pub palette: Option<Palette>,
+    pub cue: NumberCue,
+}
+
+impl NumberStyle {
+    /// Color of a number, from the palette or else the theme
+    pub fn color(&self, assets: &BoardAssets, count: u8) -> Color {
+        match self.palette {
+            Some(palette) => palette.colors()[count.clamp(1, 8) as usize - 1],
+            None => assets.bomb_counter_color(count),
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use crate::components::Coordinates;
+use crate::resources::tilemap::TileMap;
+use crate::solver::Solver;
+
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::log;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Layouts the solver may enumerate while validating a puzzle, so a pack can't stall its loading
+const VALIDATION_NODE_BUDGET: usize = 1_000_000;
+
+/// Handcrafted board, used instead of random bombs when inserted as a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub name: String,
+    pub map_size: (u16, u16),
+    pub bombs: Vec<Coordinates>,
+    /// Tiles uncovered when the board is created
+    #[serde(default)]
+    pub revealed: Vec<Coordinates>,
+    /// Tiles marked when the board is created
+    #[serde(default)]
+    pub flags: Vec<Coordinates>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PuzzleError {
+    OutOfBounds(Coordinates),
+    RevealedBomb(Coordinates),
+    MisplacedFlag(Coordinates),
+    NoUniqueSolution,
+    TooComplex,
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleError::OutOfBounds(c) => write!(f, "tile {} is out of the map", c),
+            PuzzleError::RevealedBomb(c) => write!(f, "revealed tile {} is a bomb", c),
+            PuzzleError::MisplacedFlag(c) => write!(f, "flag {} is not on a bomb", c),
+            PuzzleError::NoUniqueSolution => write!(f, "puzzle can't be solved without guessing"),
+            PuzzleError::TooComplex => write!(f, "puzzle takes too long to solve"),
+        }
+    }
+}
+
+impl std::error::Error for PuzzleError {}
+
+impl Puzzle {
+    pub fn tile_map(&self) -> TileMap {
+        let mut tile_map = TileMap::empty(self.map_size.0, self.map_size.1);
+        tile_map.place_bombs(&self.bombs);
+        tile_map
+    }
+
+    /// Checks the puzzle layout and that the solver can clear it from the revealed tiles
+    /// and flags alone, which means it has a single solution.
+    pub fn validate(&self) -> Result<(), PuzzleError> {
+        let (width, height) = self.map_size;
+        let mut all_tiles = self.bombs.iter().chain(&self.revealed).chain(&self.flags);
+        if let Some(c) = all_tiles.find(|c| c.x >= width || c.y >= height) {
+            return Err(PuzzleError::OutOfBounds(*c));
+        }
+        if let Some(c) = self.revealed.iter().find(|c| self.bombs.contains(c)) {
+            return Err(PuzzleError::RevealedBomb(*c));
+        }
+        if let Some(c) = self.flags.iter().find(|c| !self.bombs.contains(c)) {
+            return Err(PuzzleError::MisplacedFlag(*c));
+        }
+
+        let tile_map = self.tile_map();
+        let mut solver = Solver::new(&tile_map).with_node_budget(VALIDATION_NODE_BUDGET);
+        for coords in self.revealed.iter() {
+            solver.reveal(*coords);
+        }
+        for coords in self.flags.iter() {
+            solver.flag(*coords);
+        }
+        if solver.solve() {
+            Ok(())
+        } else if solver.is_exhausted() {
+            Err(PuzzleError::TooComplex)
+        } else {
+            Err(PuzzleError::NoUniqueSolution)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5d7f0a36-4a0e-4c8f-9a4b-2c1c0a6e3f11"]
+pub struct PuzzlePack {
+    pub name: String,
+    pub puzzles: Vec<Puzzle>,
+}
+
+/// Loads `.pack.ron` files, dropping every puzzle that fails validation
+#[derive(Default)]
+pub struct PuzzlePackLoader;
+
+impl AssetLoader for PuzzlePackLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let mut pack: PuzzlePack = ron::de::from_bytes(bytes)?;
+            pack.puzzles.retain(|puzzle| match puzzle.validate() {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Skipping puzzle {} of pack {}: {}", puzzle.name, pack.name, e);
+                    false
+                }
+            });
+            log::info!("Loaded puzzle pack {} ({} puzzles)", pack.name, pack.puzzles.len());
+            load_context.set_default_asset(LoadedAsset::new(pack));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pack.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn puzzle(map_size: (u16, u16), bombs: &[(u16, u16)], revealed: &[(u16, u16)]) -> Puzzle {
+        let coordinates =
+            |tiles: &[(u16, u16)]| tiles.iter().map(|&(x, y)| Coordinates { x, y }).collect();
+        Puzzle {
+            name: "Test".to_string(),
+            map_size,
+            bombs: coordinates(bombs),
+            revealed: coordinates(revealed),
+            flags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn solvable_puzzle_is_valid() {
+        assert_eq!(puzzle((3, 1), &[(0, 0)], &[(2, 0)]).validate(), Ok(()));
+    }
+
+    #[test]
+    fn tiles_off_the_map_are_refused() {
+        let error = puzzle((3, 1), &[(3, 0)], &[(0, 0)]).validate();
+        assert_eq!(error, Err(PuzzleError::OutOfBounds(Coordinates { x: 3, y: 0 })));
+    }
+
+    #[test]
+    fn revealed_bombs_and_misplaced_flags_are_refused() {
+        let error = puzzle((3, 1), &[(0, 0)], &[(0, 0)]).validate();
+        assert_eq!(error, Err(PuzzleError::RevealedBomb(Coordinates { x: 0, y: 0 })));
+
+        let mut flagged = puzzle((3, 1), &[(0, 0)], &[(2, 0)]);
+        flagged.flags.push(Coordinates { x: 1, y: 0 });
+        assert_eq!(
+            flagged.validate(),
+            Err(PuzzleError::MisplacedFlag(Coordinates { x: 1, y: 0 }))
+        );
+    }
+
+    #[test]
+    fn puzzle_needing_a_guess_is_refused() {
+        let error = puzzle((2, 2), &[(0, 0)], &[(1, 1)]).validate();
+        assert_eq!(error, Err(PuzzleError::NoUniqueSolution));
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+/// Sounds a theme can play, keys of its sound set
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum SoundEffect {
+    /// A single tile uncovered
+    Reveal,
+    /// An opening uncovered at once
+    Cascade,
+    Flag,
+    Unflag,
+    Chord,
+    Explosion,
+    Win,
+}
@@ -0,0 +1,283 @@
+use crate::raster::Canvas;
+use crate::resources::{BoardAssets, GlyphSheet, NumberCue, NumberStyle, SpriteMaterial};
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::sprite::Rect;
+
+/// Size in pixels of a tile atlas cell
+pub const TILE_ATLAS_CELL: u32 = 64;
+/// Height of the numbers drawn on tiles, relative to the tile
+const GLYPH_HEIGHT: f32 = 0.6;
+/// Pixels between atlas cells, filled with their edges so filtering doesn't bleed across cells
+const GUTTER: u32 = 2;
+
+/// Look of a tile, one cell of the tile atlas
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TileSprite {
+    Covered,
+    /// Uncovered tile showing its displayed bomb count, 0 being blank
+    Revealed(u8),
+    Flag,
+    Question,
+    Mine,
+    /// Mine that was uncovered
+    Exploded,
+    /// Flag on a tile without a mine, shown once the game is lost
+    WrongFlag,
+}
+
+impl TileSprite {
+    /// Cells in the atlas
+    pub const COUNT: usize = 15;
+
+    pub fn index(self) -> usize {
+        match self {
+            TileSprite::Covered => 0,
+            TileSprite::Revealed(count) => 1 + count.min(8) as usize,
+            TileSprite::Flag => 10,
+            TileSprite::Question => 11,
+            TileSprite::Mine => 12,
+            TileSprite::Exploded => 13,
+            TileSprite::WrongFlag => 14,
+        }
+    }
+
+    pub fn all() -> impl Iterator<Item = TileSprite> {
+        [TileSprite::Covered]
+            .into_iter()
+            .chain((0..=8).map(TileSprite::Revealed))
+            .chain([
+                TileSprite::Flag,
+                TileSprite::Question,
+                TileSprite::Mine,
+                TileSprite::Exploded,
+                TileSprite::WrongFlag,
+            ])
+    }
+}
+
+fn has_texture(material: &SpriteMaterial) -> bool {
+    material.texture != DEFAULT_IMAGE_HANDLE.typed()
+}
+
+/// Fills the canvas with a material, its texture tinted by its color
+fn paint(canvas: &mut Canvas, material: &SpriteMaterial, images: Option<&Assets<Image>>) {
+    let painted = images
+        .and_then(|images| images.get(&material.texture))
+        .is_some_and(|image| canvas.draw_image(image, material.color));
+    if !painted {
+        canvas.fill(material.color);
+    }
+}
+
+/// Draws a material texture over the canvas, or the fallback shape when the theme has none
+fn paint_icon(
+    canvas: &mut Canvas,
+    material: &SpriteMaterial,
+    images: Option<&Assets<Image>>,
+    fallback: impl FnOnce(&mut Canvas),
+) {
+    let painted = has_texture(material)
+        && images
+            .and_then(|images| images.get(&material.texture))
+            .is_some_and(|image| canvas.draw_image(image, material.color));
+    if !painted {
+        fallback(canvas);
+    }
+}
+
+fn draw_flag(canvas: &mut Canvas) {
+    let size = canvas.width as f32;
+    canvas.line(
+        Vec2::new(size * 0.38, size * 0.2),
+        Vec2::new(size * 0.38, size * 0.8),
+        size * 0.06,
+        Color::BLACK,
+    );
+    canvas.fill_rect(
+        (size * 0.25) as i32,
+        (size * 0.76) as i32,
+        (size * 0.4) as i32,
+        (size * 0.06) as i32,
+        Color::BLACK,
+    );
+    canvas.fill_triangle(
+        [
+            Vec2::new(size * 0.4, size * 0.18),
+            Vec2::new(size * 0.78, size * 0.34),
+            Vec2::new(size * 0.4, size * 0.5),
+        ],
+        Color::RED,
+    );
+}
+
+fn draw_mine(canvas: &mut Canvas) {
+    let size = canvas.width as f32;
+    let center = Vec2::splat(size / 2.);
+    for (from, to) in [
+        (Vec2::new(0.2, 0.5), Vec2::new(0.8, 0.5)),
+        (Vec2::new(0.5, 0.2), Vec2::new(0.5, 0.8)),
+        (Vec2::new(0.29, 0.29), Vec2::new(0.71, 0.71)),
+        (Vec2::new(0.29, 0.71), Vec2::new(0.71, 0.29)),
+    ] {
+        canvas.line(from * size, to * size, size * 0.06, Color::BLACK);
+    }
+    canvas.fill_circle(center, size * 0.22, Color::BLACK);
+    canvas.fill_circle(center - size * 0.07, size * 0.05, Color::WHITE);
+}
+
+/// Draws the cue of a number, telling it apart without its color
+fn draw_number_cue(canvas: &mut Canvas, cue: NumberCue, count: u8, color: Color) {
+    let size = canvas.width as f32;
+    match cue {
+        NumberCue::None => (),
+        NumberCue::Pips => {
+            let spacing = size * 0.1;
+            let left = (size - spacing * (count - 1) as f32) / 2.;
+            for i in 0..count {
+                let center = Vec2::new(left + i as f32 * spacing, size * 0.9);
+                canvas.fill_circle(center, size * 0.035, color);
+            }
+        }
+        NumberCue::Shapes => {
+            let center = Vec2::splat(size * 0.86);
+            let radius = size * 0.09;
+            let thickness = size * 0.05;
+            // Regular polygon with a corner pointing up, the canvas going down
+            let polygon = |corners: u8, turn: f32| -> Vec<Vec2> {
+                (0..corners)
+                    .map(|i| {
+                        let angle = std::f32::consts::TAU * (i as f32 + turn) / corners as f32;
+                        center + Vec2::new(angle.sin(), -angle.cos()) * radius
+                    })
+                    .collect()
+            };
+            match count {
+                1 => canvas.fill_circle(center, radius * 0.5, color),
+                2 => canvas.line(
+                    center - Vec2::new(radius, 0.),
+                    center + Vec2::new(radius, 0.),
+                    thickness,
+                    color,
+                ),
+                3 => canvas.fill_polygon(&polygon(3, 0.), color),
+                4 => canvas.fill_polygon(&polygon(4, 0.5), color),
+                5 => canvas.fill_polygon(&polygon(4, 0.), color),
+                6 => canvas.fill_polygon(&polygon(6, 0.), color),
+                7 => canvas.ring(center, radius, thickness, color),
+                _ => {
+                    canvas.line(center - Vec2::new(radius, 0.), center + Vec2::new(radius, 0.), thickness, color);
+                    canvas.line(center - Vec2::new(0., radius), center + Vec2::new(0., radius), thickness, color);
+                }
+            }
+        }
+    }
+}
+
+/// Draws one atlas cell from the theme materials, textures missing from `images` are drawn as
+/// their plain color
+pub(crate) fn draw_tile(
+    assets: &BoardAssets,
+    style: &NumberStyle,
+    glyphs: &GlyphSheet,
+    sprite: TileSprite,
+    images: Option<&Assets<Image>>,
+) -> Canvas {
+    let size = TILE_ATLAS_CELL;
+    let mut canvas = Canvas::new(size, size);
+    match sprite {
+        TileSprite::Covered | TileSprite::Flag | TileSprite::Question => {
+            paint(&mut canvas, &assets.covered_tile_material, images);
+            // Raised look
+            let bevel = (size / 16) as i32;
+            let size = size as i32;
+            canvas.fill_rect(0, 0, size, bevel, Color::rgba(1., 1., 1., 0.35));
+            canvas.fill_rect(0, bevel, bevel, size - bevel, Color::rgba(1., 1., 1., 0.35));
+            canvas.fill_rect(bevel, size - bevel, size - bevel, bevel, Color::rgba(0., 0., 0., 0.35));
+            canvas.fill_rect(size - bevel, bevel, bevel, size - 2 * bevel, Color::rgba(0., 0., 0., 0.35));
+        }
+        _ => paint(&mut canvas, &assets.tile_material, images),
+    }
+    let glyph_height = size as f32 * GLYPH_HEIGHT;
+    match sprite {
+        TileSprite::Covered | TileSprite::Revealed(0) => (),
+        TileSprite::Revealed(count) => {
+            let digit = char::from_digit(count as u32, 10).unwrap_or('?');
+            let color = style.color(assets, count);
+            glyphs.draw(&mut canvas, digit, glyph_height, color);
+            draw_number_cue(&mut canvas, style.cue, count, color);
+        }
+        TileSprite::Flag => paint_icon(&mut canvas, &assets.flag_material, images, draw_flag),
+        TileSprite::Question => glyphs.draw(&mut canvas, '?', glyph_height, Color::WHITE),
+        TileSprite::Mine => paint_icon(&mut canvas, &assets.bomb_material, images, draw_mine),
+        TileSprite::Exploded => {
+            canvas.fill(Color::rgba(1., 0., 0., 0.8));
+            paint_icon(&mut canvas, &assets.bomb_material, images, draw_mine);
+        }
+        TileSprite::WrongFlag => {
+            paint_icon(&mut canvas, &assets.bomb_material, images, draw_mine);
+            let size = size as f32;
+            let thickness = size * 0.08;
+            canvas.line(Vec2::splat(size * 0.2), Vec2::splat(size * 0.8), thickness, Color::RED);
+            canvas.line(
+                Vec2::new(size * 0.2, size * 0.8),
+                Vec2::new(size * 0.8, size * 0.2),
+                thickness,
+                Color::RED,
+            );
+        }
+    }
+    canvas
+}
+
+/// Glyphs of the numbers drawn in the tile atlas, baked at the size they are drawn at
+pub(crate) fn tile_glyphs(
+    assets: &BoardAssets,
+    images: Option<&Assets<Image>>,
+    fonts: Option<&Assets<Font>>,
+) -> GlyphSheet {
+    let height = (TILE_ATLAS_CELL as f32 * GLYPH_HEIGHT).round() as u32;
+    GlyphSheet::for_theme(assets, height, images, fonts)
+}
+
+/// Draws every tile look of a theme into a texture atlas indexed by `TileSprite::index`, with
+/// numbers from the theme glyph sheet or font
+pub fn build_tile_atlas(
+    assets: &BoardAssets,
+    style: &NumberStyle,
+    images: &mut Assets<Image>,
+    fonts: &Assets<Font>,
+) -> TextureAtlas {
+    let glyphs = tile_glyphs(assets, Some(&*images), Some(fonts));
+    let stride = TILE_ATLAS_CELL + 2 * GUTTER;
+    let mut canvas = Canvas::new(stride * TileSprite::COUNT as u32, stride);
+    let mut cells = Vec::with_capacity(TileSprite::COUNT);
+    for sprite in TileSprite::all() {
+        let cell = draw_tile(assets, style, &glyphs, sprite, Some(&*images));
+        let x = (sprite.index() as u32 * stride + GUTTER) as i32;
+        let y = GUTTER as i32;
+        canvas.copy_from(&cell, x, y);
+        // Extrudes the cell edges into its gutter
+        let last = TILE_ATLAS_CELL as i32 - 1;
+        for py in -(GUTTER as i32)..=last + GUTTER as i32 {
+            for px in -(GUTTER as i32)..=last + GUTTER as i32 {
+                if (0..=last).contains(&px) && (0..=last).contains(&py) {
+                    continue;
+                }
+                let edge = cell.pixel(px.clamp(0, last), py.clamp(0, last));
+                canvas.set_pixel(x + px, y + py, edge);
+            }
+        }
+        cells.push(Rect {
+            min: Vec2::new(x as f32, y as f32),
+            max: Vec2::new(x as f32, y as f32) + Vec2::splat(TILE_ATLAS_CELL as f32),
+        });
+    }
+
+    let size = Vec2::new(canvas.width as f32, canvas.height as f32);
+    let mut atlas = TextureAtlas::new_empty(images.add(canvas.into_image()), size);
+    for cell in cells {
+        atlas.add_texture(cell);
+    }
+    atlas
+}
@@ -0,0 +1,43 @@
+use crate::{Coordinates, TileMap};
+use bevy::prelude::KeyCode;
+
+/// Keys the tile cursor moves, jumps, marks and chords with, space reveals as the pan key of
+/// `CameraControls`
+pub const CURSOR_KEYS: [KeyCode; 11] = [
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Down,
+    KeyCode::Up,
+    KeyCode::H,
+    KeyCode::J,
+    KeyCode::K,
+    KeyCode::L,
+    KeyCode::Tab,
+    KeyCode::F,
+    KeyCode::D,
+];
+
+/// Tile targeted by the keyboard, following the mouse when it moves over the board
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TileCursor(pub Option<Coordinates>);
+
+impl TileCursor {
+    /// Cursor position, starting from the middle of the board when there is none yet
+    pub fn get_or_center(&self, tile_map: &TileMap) -> Coordinates {
+        self.0.unwrap_or(Coordinates {
+            x: tile_map.width / 2,
+            y: tile_map.height / 2,
+        })
+    }
+
+    /// Cursor position moved by `(dx, dy)` tiles, staying on the board
+    pub fn step(&self, tile_map: &TileMap, (dx, dy): (i8, i8)) -> Coordinates {
+        let current = self.get_or_center(tile_map);
+        let x = (current.x as i32 + dx as i32).clamp(0, tile_map.width as i32 - 1);
+        let y = (current.y as i32 + dy as i32).clamp(0, tile_map.height as i32 - 1);
+        Coordinates {
+            x: x as u16,
+            y: y as u16,
+        }
+    }
+}
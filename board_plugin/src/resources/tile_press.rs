@@ -0,0 +1,35 @@
+use crate::{Board, Coordinates};
+use bevy::prelude::MouseButton;
+
+/// Mouse button held down on a tile, its action runs when it is released over the same tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Press {
+    pub button: MouseButton,
+    pub coordinates: Coordinates,
+    /// Whether the mouse is over the pressed tile, releasing it anywhere else cancels the action
+    pub over: bool,
+}
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct TilePress(pub Option<Press>);
+
+impl TilePress {
+    /// Tiles drawn pressed in: the tile about to be uncovered, or its covered neighborhood
+    /// for a chord
+    pub fn pressed_tiles(&self, board: &Board) -> Vec<Coordinates> {
+        match self.0 {
+            Some(press) if press.over => match press.button {
+                MouseButton::Left if board.can_uncover(press.coordinates) => {
+                    vec![press.coordinates]
+                }
+                MouseButton::Middle => board
+                    .neighbors(press.coordinates)
+                    .chain([press.coordinates])
+                    .filter(|c| board.can_uncover(*c))
+                    .collect(),
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+}
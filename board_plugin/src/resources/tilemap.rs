@@ -1,5 +1,7 @@
 use crate::components::Coordinates;
 use crate::resources::tile::Tile;
+use crate::resources::NumberRule;
+use crate::solver::Solver;
 
 use rand::{thread_rng, Rng};
 use std::ops::{Deref, DerefMut};
@@ -20,31 +22,39 @@ pub struct TileMap {
     pub bomb_count: u16,
     pub height: u16,
     pub width: u16,
+    pub number_rule: NumberRule,
     map: Vec<Vec<Tile>>,
+    displayed_counts: Vec<Vec<u8>>,
 }
 
 impl TileMap {
     pub fn empty(width: u16, height: u16) -> Self {
         let map = (0..height)
-            .into_iter()
-            .map(|_| (0..width).into_iter().map(|_| Tile::Empty).collect())
+            .map(|_| (0..width).map(|_| Tile::Empty).collect())
             .collect();
 
         Self {
             bomb_count: 0,
             height,
             width,
+            number_rule: NumberRule::Classic,
             map,
+            displayed_counts: vec![vec![0; width as usize]; height as usize],
         }
     }
 
+    pub fn with_number_rule(mut self, number_rule: NumberRule) -> Self {
+        self.number_rule = number_rule;
+        self
+    }
+
     #[cfg(feature = "debug")]
     pub fn console_output(&self) -> String {
         let mut buffer = format!(
             "Map ({}, {}) with {} bombs:\n",
             self.width, self.height, self.bomb_count
         );
-        let line: String = (0..=(self.width + 1)).into_iter().map(|_| '-').collect();
+        let line: String = (0..=(self.width + 1)).map(|_| '-').collect();
         buffer = format!("{}{}\n", buffer, line);
 
         for line in self.iter().rev() {
@@ -83,10 +93,114 @@ impl TileMap {
         res as u8
     }
 
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Count shown to the player on the tile, which only matches `bomb_count_at` with the
+    /// classic number rule
+    pub fn displayed_count_at(&self, coordinates: Coordinates) -> u8 {
+        if coordinates.x >= self.width || coordinates.y >= self.height {
+            return 0;
+        }
+        self.displayed_counts[coordinates.y as usize][coordinates.x as usize]
+    }
+
+    /// Groups of adjacent empty tiles, each uncovered at once by a single click
+    pub fn openings(&self) -> Vec<Vec<Coordinates>> {
+        let mut visited = vec![vec![false; self.width as usize]; self.height as usize];
+        let mut openings = Vec::new();
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if visited[y as usize][x as usize] || self[y as usize][x as usize] != Tile::Empty {
+                    continue;
+                }
+                visited[y as usize][x as usize] = true;
+                let mut opening = Vec::new();
+                let mut to_visit = vec![Coordinates { x, y }];
+                while let Some(coords) = to_visit.pop() {
+                    opening.push(coords);
+                    for neighbor in self.safe_square_at(coords) {
+                        if neighbor.x >= self.width || neighbor.y >= self.height {
+                            continue;
+                        }
+                        let (nx, ny) = (neighbor.x as usize, neighbor.y as usize);
+                        if !visited[ny][nx] && self[ny][nx] == Tile::Empty {
+                            visited[ny][nx] = true;
+                            to_visit.push(neighbor);
+                        }
+                    }
+                }
+                openings.push(opening);
+            }
+        }
+        openings
+    }
+
+    /// Bechtel's Board Benchmark Value: the minimum number of clicks needed to clear the board
+    /// without flagging
+    pub fn bbbv(&self) -> u32 {
+        let isolated_numbers = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coordinates { x, y }))
+            .filter(|c| matches!(self[c.y as usize][c.x as usize], Tile::BombNeighbor(_)))
+            .filter(|c| {
+                !self.safe_square_at(*c).any(|n| {
+                    n.x < self.width && n.y < self.height && self[n.y as usize][n.x as usize] == Tile::Empty
+                })
+            })
+            .count();
+        (self.openings().len() + isolated_numbers) as u32
+    }
+
+    /// First empty tile, uncovered on its own when the board has a safe start
+    pub fn safe_start(&self) -> Option<Coordinates> {
+        self.iter().enumerate().find_map(|(y, line)| {
+            line.iter().position(|tile| *tile == Tile::Empty).map(|x| Coordinates {
+                x: x as u16,
+                y: y as u16,
+            })
+        })
+    }
+
+    /// Places bombs at random until the board can be cleared from its safe start without
+    /// guessing, giving up once `work_budget` is spent. Every attempt costs its tile count plus
+    /// the layouts the solver enumerated. Returns whether a solvable board was found.
+    pub fn set_solvable_bombs<R: Rng>(
+        &mut self,
+        bomb_count: u16,
+        work_budget: usize,
+        rng: &mut R,
+    ) -> bool {
+        let tile_count = self.width as usize * self.height as usize;
+        let mut spent = 0;
+        while spent < work_budget {
+            self.clear();
+            self.set_bombs(bomb_count, rng);
+            spent += tile_count;
+            let (solvable, nodes) = self.solve_within(work_budget.saturating_sub(spent));
+            if solvable {
+                return true;
+            }
+            spent += nodes;
+        }
+        false
+    }
+
+    pub fn is_solvable(&self) -> bool {
+        self.solve_within(usize::MAX).0
+    }
+
+    /// Whether the solver clears the board from its safe start enumerating at most
+    /// `node_budget` layouts, and how many it enumerated
+    fn solve_within(&self, node_budget: usize) -> (bool, usize) {
+        let start = match self.safe_start() {
+            Some(s) => s,
+            None => return (false, 0),
+        };
+        let mut solver = Solver::new(self).with_node_budget(node_budget);
+        solver.reveal(start);
+        (solver.solve(), solver.nodes())
+    }
+
+    pub fn set_bombs<R: Rng>(&mut self, bomb_count: u16, rng: &mut R) {
         self.bomb_count = bomb_count;
         let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
 
         while remaining_bombs > 0 {
             let (x, y) = (
@@ -99,6 +213,26 @@ impl TileMap {
             }
         }
 
+        self.set_bomb_neighbors(rng);
+    }
+
+    /// Places bombs on the given coordinates instead of random ones, for handcrafted boards
+    pub fn place_bombs(&mut self, bombs: &[Coordinates]) {
+        for coords in bombs {
+            if coords.x >= self.width || coords.y >= self.height {
+                continue;
+            }
+            let tile = &mut self[coords.y as usize][coords.x as usize];
+            if !tile.is_bomb() {
+                *tile = Tile::Bomb;
+                self.bomb_count += 1;
+            }
+        }
+
+        self.set_bomb_neighbors(&mut thread_rng());
+    }
+
+    fn set_bomb_neighbors<R: Rng>(&mut self, rng: &mut R) {
         for y in 0..self.height {
             for x in 0..self.width {
                 let coords = Coordinates { x, y };
@@ -112,9 +246,21 @@ impl TileMap {
                 }
                 let tile = &mut self[y as usize][x as usize];
                 *tile = Tile::BombNeighbor(num);
+                self.displayed_counts[y as usize][x as usize] =
+                    self.number_rule.displayed_count(num, rng);
             }
         }
     }
+
+    fn clear(&mut self) {
+        self.bomb_count = 0;
+        for line in self.map.iter_mut() {
+            line.fill(Tile::Empty);
+        }
+        for line in self.displayed_counts.iter_mut() {
+            line.fill(0);
+        }
+    }
 }
 
 impl Deref for TileMap {
@@ -0,0 +1,538 @@
+use crate::components::Coordinates;
+use crate::resources::tile::Tile;
+use crate::resources::tilemap::TileMap;
+use crate::resources::Board;
+
+use bevy::utils::{HashMap, HashSet};
+
+/// Frontier groups bigger than this are not enumerated, the solver gives up on them instead
+const MAX_GROUP_SIZE: usize = 24;
+
+/// Layouts tried on a single group before giving up on it like on a group too big, as weak
+/// constraints such as liar numbers barely prune the enumeration
+const MAX_GROUP_NODES: usize = 20_000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Deduction {
+    Safe(Coordinates),
+    Bomb(Coordinates),
+}
+
+/// A revealed number and the covered, unresolved tiles around it
+#[derive(Debug, Clone)]
+struct Constraint {
+    tiles: Vec<Coordinates>,
+    /// Bit mask of the bomb counts the tiles can hold, as the number rule may hide the
+    /// actual one
+    bombs: u16,
+}
+
+/// Bomb layouts of a group of frontier tiles
+#[derive(Debug, Clone)]
+struct GroupLayouts {
+    tiles: Vec<Coordinates>,
+    /// Layouts with a bomb on each tile of `tiles`
+    bomb_counts: Vec<u32>,
+    solutions: u32,
+}
+
+/// Bit mask of every count from `min` to `max` included
+const fn count_range(min: usize, max: usize) -> u16 {
+    ((1 << (max + 1)) - 1) & !((1 << min) - 1)
+}
+
+/// Plays a `TileMap` the way a player would, only using what is visible on the board,
+/// to find out whether it can be cleared without guessing. Revealed numbers are read through
+/// the map's number rule, like the player sees them.
+#[derive(Debug, Clone)]
+pub struct Solver<'a> {
+    tile_map: &'a TileMap,
+    revealed: HashSet<Coordinates>,
+    bombs: HashSet<Coordinates>,
+    /// Layouts tried while enumerating groups so far
+    nodes: usize,
+    /// Layouts the solver may try in total before it stops enumerating
+    node_budget: usize,
+}
+
+impl<'a> Solver<'a> {
+    pub fn new(tile_map: &'a TileMap) -> Self {
+        Self {
+            tile_map,
+            revealed: HashSet::default(),
+            bombs: HashSet::default(),
+            nodes: 0,
+            node_budget: usize::MAX,
+        }
+    }
+
+    /// Stops enumerating layouts once `node_budget` of them were tried, only the simpler
+    /// deductions are made past that
+    pub fn with_node_budget(mut self, node_budget: usize) -> Self {
+        self.node_budget = node_budget;
+        self
+    }
+
+    /// Layouts tried while enumerating groups so far, the bulk of the solver's work
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+
+    /// Whether the solver ran out of its node budget
+    pub fn is_exhausted(&self) -> bool {
+        self.nodes >= self.node_budget
+    }
+
+    /// Solver knowing the tiles the player uncovered on a board, flags are left out as they
+    /// aren't proven
+    pub fn for_board(board: &'a Board) -> Self {
+        let mut solver = Self::new(&board.tile_map);
+        for coords in board.coordinates() {
+            if !board.is_covered(coords) {
+                solver.reveal(coords);
+            }
+        }
+        solver
+    }
+
+    /// Reveals a tile, cascading through empty tiles like the board does
+    pub fn reveal(&mut self, coordinates: Coordinates) {
+        let mut to_reveal = vec![coordinates];
+        while let Some(coords) = to_reveal.pop() {
+            if !self.in_bounds(coords) || self.tile_map.is_bomb_at(coords) {
+                continue;
+            }
+            if !self.revealed.insert(coords) {
+                continue;
+            }
+            if self.tile(coords) == Tile::Empty {
+                to_reveal.extend(self.neighbors(coords));
+            }
+        }
+    }
+
+    /// Marks a tile as a known bomb
+    pub fn flag(&mut self, coordinates: Coordinates) {
+        if self.in_bounds(coordinates) && !self.revealed.contains(&coordinates) {
+            self.bombs.insert(coordinates);
+        }
+    }
+
+    pub fn is_revealed(&self, coordinates: Coordinates) -> bool {
+        self.revealed.contains(&coordinates)
+    }
+
+    pub fn is_solved(&self) -> bool {
+        let tile_count = self.tile_map.width as usize * self.tile_map.height as usize;
+        self.revealed.len() + self.tile_map.bomb_count as usize == tile_count
+    }
+
+    /// Applies deductions until the board is cleared or no tile can be resolved without
+    /// guessing. Returns whether the board was cleared.
+    pub fn solve(&mut self) -> bool {
+        while !self.is_solved() {
+            let deductions = self.deductions();
+            if deductions.is_empty() {
+                return false;
+            }
+            for deduction in deductions {
+                match deduction {
+                    Deduction::Safe(coords) => self.reveal(coords),
+                    Deduction::Bomb(coords) => self.flag(coords),
+                }
+            }
+        }
+        true
+    }
+
+    /// Finds a tile that can be proven safe, flagging the proven bombs on the way
+    pub fn next_safe_tile(&mut self) -> Option<Coordinates> {
+        loop {
+            let deductions = self.deductions();
+            if deductions.is_empty() {
+                return None;
+            }
+            for deduction in deductions {
+                match deduction {
+                    Deduction::Safe(coords) => return Some(coords),
+                    Deduction::Bomb(coords) => self.flag(coords),
+                }
+            }
+        }
+    }
+
+    /// Every tile that can be proven safe or proven to be a bomb from the current state
+    pub fn deductions(&mut self) -> Vec<Deduction> {
+        let constraints = self.constraints();
+
+        let deductions = Self::trivial_deductions(&constraints);
+        if !deductions.is_empty() {
+            return deductions;
+        }
+
+        let deductions = self.enumerated_deductions(&constraints);
+        if !deductions.is_empty() {
+            return deductions;
+        }
+
+        self.global_deductions()
+    }
+
+    fn in_bounds(&self, coords: Coordinates) -> bool {
+        coords.x < self.tile_map.width && coords.y < self.tile_map.height
+    }
+
+    fn tile(&self, coords: Coordinates) -> Tile {
+        self.tile_map[coords.y as usize][coords.x as usize]
+    }
+
+    fn neighbors(&self, coords: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
+        self.tile_map
+            .safe_square_at(coords)
+            .filter(move |c| self.in_bounds(*c))
+    }
+
+    fn is_unresolved(&self, coords: Coordinates) -> bool {
+        !self.revealed.contains(&coords) && !self.bombs.contains(&coords)
+    }
+
+    fn unresolved_tiles(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        let width = self.tile_map.width;
+        (0..self.tile_map.height)
+            .flat_map(move |y| (0..width).map(move |x| Coordinates { x, y }))
+            .filter(|c| self.is_unresolved(*c))
+    }
+
+    fn constraints(&self) -> Vec<Constraint> {
+        let mut constraints = Vec::new();
+        for coords in self.revealed.iter().copied() {
+            if !matches!(self.tile(coords), Tile::BombNeighbor(_)) {
+                continue;
+            }
+            let displayed = self.tile_map.displayed_count_at(coords);
+            let tiles: Vec<_> = self
+                .neighbors(coords)
+                .filter(|c| self.is_unresolved(*c))
+                .collect();
+            if tiles.is_empty() {
+                continue;
+            }
+            let known = self.neighbors(coords).filter(|c| self.bombs.contains(c)).count();
+            constraints.push(Constraint {
+                tiles,
+                bombs: self.tile_map.number_rule.possible_counts(displayed) >> known,
+            });
+        }
+        constraints
+    }
+
+    fn trivial_deductions(constraints: &[Constraint]) -> Vec<Deduction> {
+        let mut deductions = HashSet::default();
+        for constraint in constraints {
+            if constraint.bombs == count_range(0, 0) {
+                deductions.extend(constraint.tiles.iter().map(|c| Deduction::Safe(*c)));
+            } else if constraint.bombs == count_range(constraint.tiles.len(), constraint.tiles.len()) {
+                deductions.extend(constraint.tiles.iter().map(|c| Deduction::Bomb(*c)));
+            }
+        }
+        deductions.into_iter().collect()
+    }
+
+    /// Enumerates every bomb layout of each independent group of frontier tiles, keeping the
+    /// tiles that are safe, or bombs, in all of them
+    fn enumerated_deductions(&mut self, constraints: &[Constraint]) -> Vec<Deduction> {
+        let mut deductions = Vec::new();
+        for group in self.enumerate_groups(constraints) {
+            for (tile, count) in group.tiles.iter().zip(group.bomb_counts) {
+                if count == 0 {
+                    deductions.push(Deduction::Safe(*tile));
+                } else if count == group.solutions {
+                    deductions.push(Deduction::Bomb(*tile));
+                }
+            }
+        }
+        deductions
+    }
+
+    /// Chance of every unresolved tile to hold a bomb. Frontier tiles get the share of the
+    /// layouts of their group with a bomb there. In groups too big to enumerate they get the
+    /// average share of the numbers around them, unless one number proves them. The other tiles
+    /// split the bombs left evenly. It is an estimate, as layouts aren't weighted by how many
+    /// ways the rest of the board fits them
+    pub fn probabilities(&mut self) -> HashMap<Coordinates, f32> {
+        let constraints = self.constraints();
+        let mut probabilities: HashMap<_, _> = Self::trivial_deductions(&constraints)
+            .into_iter()
+            .map(|deduction| match deduction {
+                Deduction::Safe(c) => (c, 0.),
+                Deduction::Bomb(c) => (c, 1.),
+            })
+            .collect();
+        for group in self.enumerate_groups(&constraints) {
+            for (tile, count) in group.tiles.iter().zip(group.bomb_counts) {
+                probabilities.insert(*tile, count as f32 / group.solutions as f32);
+            }
+        }
+        let mut shares: HashMap<Coordinates, (f32, u32)> = HashMap::default();
+        for constraint in constraints.iter() {
+            let counts: Vec<_> = (0..=constraint.tiles.len())
+                .filter(|count| constraint.bombs & (1 << count) != 0)
+                .collect();
+            if counts.is_empty() {
+                continue;
+            }
+            let mean = counts.iter().sum::<usize>() as f32 / counts.len() as f32;
+            for tile in constraint.tiles.iter().filter(|t| !probabilities.contains_key(t)) {
+                let share = shares.entry(*tile).or_insert((0., 0));
+                share.0 += mean / constraint.tiles.len() as f32;
+                share.1 += 1;
+            }
+        }
+        probabilities.extend(
+            shares
+                .into_iter()
+                .map(|(tile, (total, count))| (tile, (total / count as f32).min(1.))),
+        );
+
+        let expected_bombs: f32 = probabilities.values().sum();
+        let others: Vec<_> = self
+            .unresolved_tiles()
+            .filter(|c| !probabilities.contains_key(c))
+            .collect();
+        let remaining = self.tile_map.bomb_count as f32 - self.bombs.len() as f32 - expected_bombs;
+        let density = (remaining / others.len().max(1) as f32).clamp(0., 1.);
+        probabilities.extend(others.into_iter().map(|c| (c, density)));
+        probabilities
+    }
+
+    /// Bomb layouts of every independent group of frontier tiles small enough to enumerate
+    /// within the node limits, groups without any layout left out
+    fn enumerate_groups(&mut self, constraints: &[Constraint]) -> Vec<GroupLayouts> {
+        let mut layouts = Vec::new();
+        for group in Self::groups(constraints) {
+            let tiles: Vec<Coordinates> = {
+                let mut tiles: Vec<_> = group
+                    .iter()
+                    .flat_map(|c| constraints[*c].tiles.iter().copied())
+                    .collect::<HashSet<_>>()
+                    .into_iter()
+                    .collect();
+                tiles.sort();
+                tiles
+            };
+            if tiles.len() > MAX_GROUP_SIZE {
+                continue;
+            }
+            let index: HashMap<Coordinates, usize> =
+                tiles.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+            let group_constraints: Vec<(Vec<usize>, u16)> = group
+                .iter()
+                .map(|c| {
+                    let constraint = &constraints[*c];
+                    (
+                        constraint.tiles.iter().map(|t| index[t]).collect(),
+                        constraint.bombs,
+                    )
+                })
+                .collect();
+
+            let mut layout = vec![None; tiles.len()];
+            let mut bomb_counts = vec![0_u32; tiles.len()];
+            let mut solutions = 0_u32;
+            let mut nodes = 0;
+            let max_nodes = MAX_GROUP_NODES.min(self.node_budget.saturating_sub(self.nodes));
+            let complete = Self::enumerate(
+                &group_constraints,
+                &mut layout,
+                0,
+                &mut bomb_counts,
+                &mut solutions,
+                &mut nodes,
+                max_nodes,
+            );
+            self.nodes += nodes;
+            if !complete || solutions == 0 {
+                continue;
+            }
+            layouts.push(GroupLayouts {
+                tiles,
+                bomb_counts,
+                solutions,
+            });
+        }
+        layouts
+    }
+
+    /// Returns false when it gave up after `max_nodes` layouts
+    fn enumerate(
+        constraints: &[(Vec<usize>, u16)],
+        layout: &mut Vec<Option<bool>>,
+        next: usize,
+        bomb_counts: &mut [u32],
+        solutions: &mut u32,
+        nodes: &mut usize,
+        max_nodes: usize,
+    ) -> bool {
+        if *nodes >= max_nodes {
+            return false;
+        }
+        *nodes += 1;
+        let consistent = constraints.iter().all(|(tiles, bombs)| {
+            let placed = tiles.iter().filter(|t| layout[**t] == Some(true)).count();
+            let open = tiles.iter().filter(|t| layout[**t].is_none()).count();
+            bombs & count_range(placed, placed + open) != 0
+        });
+        if !consistent {
+            return true;
+        }
+        if next == layout.len() {
+            *solutions += 1;
+            for (count, bomb) in bomb_counts.iter_mut().zip(layout.iter()) {
+                if *bomb == Some(true) {
+                    *count += 1;
+                }
+            }
+            return true;
+        }
+        for bomb in [false, true] {
+            layout[next] = Some(bomb);
+            let complete = Self::enumerate(
+                constraints,
+                layout,
+                next + 1,
+                bomb_counts,
+                solutions,
+                nodes,
+                max_nodes,
+            );
+            if !complete {
+                layout[next] = None;
+                return false;
+            }
+        }
+        layout[next] = None;
+        true
+    }
+
+    /// Splits constraints into groups sharing no tile, as indices into `constraints`
+    fn groups(constraints: &[Constraint]) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut visited = vec![false; constraints.len()];
+        for start in 0..constraints.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut group = vec![start];
+            let mut i = 0;
+            while i < group.len() {
+                let current = &constraints[group[i]];
+                for (other, constraint) in constraints.iter().enumerate() {
+                    if !visited[other] && constraint.tiles.iter().any(|t| current.tiles.contains(t))
+                    {
+                        visited[other] = true;
+                        group.push(other);
+                    }
+                }
+                i += 1;
+            }
+            groups.push(group);
+        }
+        groups
+    }
+
+    /// Uses the total bomb count once nothing else can be proven
+    fn global_deductions(&self) -> Vec<Deduction> {
+        let unresolved: Vec<_> = self.unresolved_tiles().collect();
+        let remaining = (self.tile_map.bomb_count as usize).saturating_sub(self.bombs.len());
+        if remaining == 0 {
+            unresolved.into_iter().map(Deduction::Safe).collect()
+        } else if remaining == unresolved.len() {
+            unresolved.into_iter().map(Deduction::Bomb).collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile_map(width: u16, height: u16, bombs: &[(u16, u16)]) -> TileMap {
+        let bombs: Vec<_> = bombs.iter().map(|&(x, y)| Coordinates { x, y }).collect();
+        let mut tile_map = TileMap::empty(width, height);
+        tile_map.place_bombs(&bombs);
+        tile_map
+    }
+
+    #[test]
+    fn count_range_sets_every_count_between_its_bounds() {
+        assert_eq!(count_range(0, 0), 0b1);
+        assert_eq!(count_range(2, 3), 0b1100);
+        assert_eq!(count_range(1, 8), 0b1_1111_1110);
+    }
+
+    #[test]
+    fn number_with_as_many_covered_tiles_as_bombs_proves_them() {
+        // The revealed corner cascades to the 1 next to the bomb
+        let tile_map = tile_map(3, 1, &[(0, 0)]);
+        let mut solver = Solver::new(&tile_map);
+        solver.reveal(Coordinates { x: 2, y: 0 });
+        assert!(solver.is_revealed(Coordinates { x: 1, y: 0 }));
+        assert_eq!(solver.deductions(), vec![Deduction::Bomb(Coordinates { x: 0, y: 0 })]);
+        assert!(solver.solve());
+    }
+
+    #[test]
+    fn number_with_its_bombs_flagged_proves_the_other_tiles_safe() {
+        let tile_map = tile_map(3, 1, &[(0, 0)]);
+        let mut solver = Solver::new(&tile_map);
+        solver.reveal(Coordinates { x: 1, y: 0 });
+        solver.flag(Coordinates { x: 0, y: 0 });
+        assert_eq!(solver.deductions(), vec![Deduction::Safe(Coordinates { x: 2, y: 0 })]);
+    }
+
+    #[test]
+    fn overlapping_numbers_are_enumerated() {
+        // Three 1s over a covered row, only the middle tile touches all of them
+        let tile_map = tile_map(3, 2, &[(1, 0)]);
+        let mut solver = Solver::new(&tile_map);
+        solver.reveal(Coordinates { x: 0, y: 1 });
+        solver.reveal(Coordinates { x: 2, y: 1 });
+        solver.reveal(Coordinates { x: 1, y: 1 });
+        let mut deductions = solver.deductions();
+        deductions.sort_by_key(|d| match d {
+            Deduction::Safe(c) | Deduction::Bomb(c) => c.x,
+        });
+        assert_eq!(
+            deductions,
+            vec![
+                Deduction::Safe(Coordinates { x: 0, y: 0 }),
+                Deduction::Bomb(Coordinates { x: 1, y: 0 }),
+                Deduction::Safe(Coordinates { x: 2, y: 0 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn spent_node_budget_stops_the_enumeration() {
+        let tile_map = tile_map(3, 2, &[(1, 0)]);
+        let mut solver = Solver::new(&tile_map).with_node_budget(3);
+        solver.reveal(Coordinates { x: 0, y: 1 });
+        solver.reveal(Coordinates { x: 2, y: 1 });
+        solver.reveal(Coordinates { x: 1, y: 1 });
+        assert!(solver.deductions().is_empty());
+        assert_eq!(solver.nodes(), 3);
+        assert!(solver.is_exhausted());
+    }
+
+    #[test]
+    fn board_needing_a_guess_is_not_solved() {
+        let tile_map = tile_map(2, 2, &[(0, 0)]);
+        let mut solver = Solver::new(&tile_map);
+        solver.reveal(Coordinates { x: 1, y: 1 });
+        assert!(solver.deductions().is_empty());
+        assert!(!solver.solve());
+    }
+}
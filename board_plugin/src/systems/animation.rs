@@ -0,0 +1,152 @@
+use crate::resources::{AnimationOptions, TileChange, TileSprite, TileState};
+use crate::{Board, BoardCompletedEvent, Coordinates};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::f32::consts::PI;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum TweenKind {
+    /// Drawn as is once started
+    Instant,
+    /// Cover flipping over, the tile shows its new look from the middle of the flip
+    Reveal,
+    /// Flag dropping from above the tile
+    Flag,
+    /// Mine going off
+    Explosion,
+    /// Bounce of a tile of a completed board, its look doesn't change
+    Celebration,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct TileTween {
+    pub coordinates: Coordinates,
+    pub kind: TweenKind,
+    /// Time since startup the tween starts at
+    pub start: f64,
+    pub duration: f32,
+}
+
+impl TileTween {
+    /// Progress between 0 and 1, `None` before the tween starts
+    pub fn progress(&self, now: f64) -> Option<f32> {
+        let elapsed = (now - self.start) as f32;
+        if elapsed < 0. {
+            None
+        } else if self.duration <= 0. {
+            Some(1.)
+        } else {
+            Some((elapsed / self.duration).min(1.))
+        }
+    }
+
+    /// Whether the tile shows its new look at `progress`
+    pub fn shows_change(&self, progress: f32) -> bool {
+        self.kind != TweenKind::Reveal || progress >= 0.5
+    }
+
+    /// Scale of the tile and alpha of its sprite at `progress`
+    pub fn look(&self, progress: f32) -> (Vec2, Color) {
+        let bump = (progress * PI).sin();
+        match self.kind {
+            TweenKind::Instant => (Vec2::ONE, Color::WHITE),
+            TweenKind::Reveal => (Vec2::new((1. - 2. * progress).abs(), 1.), Color::WHITE),
+            TweenKind::Flag => {
+                let fall = 1. - progress * (2. - progress);
+                (Vec2::splat(1. + fall), Color::rgba(1., 1., 1., progress))
+            }
+            TweenKind::Explosion => (Vec2::splat(1. + 0.5 * bump), Color::WHITE),
+            TweenKind::Celebration => (
+                Vec2::splat(1. + 0.2 * bump),
+                Color::rgb(1., 1., 1. - 0.6 * bump),
+            ),
+        }
+    }
+}
+
+/// Tile changes waiting to be drawn or being animated, oldest first
+#[derive(Debug, Default)]
+pub(crate) struct TileTweens(pub Vec<TileTween>);
+
+/// Turns the tile changes of the board into tweens, delayed by their distance to the tile they
+/// spread from
+pub fn schedule_tile_tweens(
+    mut board: ResMut<Board>,
+    options: Option<Res<AnimationOptions>>,
+    time: Res<Time>,
+    mut tweens: ResMut<TileTweens>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    let options = options.map(|o| o.clone()).unwrap_or_default();
+    let now = time.seconds_since_startup();
+    let changed = board.take_changed();
+    let completed = board_completed_evr.iter().count() > 0;
+    if changed.is_empty() && !completed {
+        return;
+    }
+    if options.reduce_motion {
+        tweens.0.extend(changed.into_iter().map(|change| TileTween {
+            coordinates: change.coordinates,
+            kind: TweenKind::Instant,
+            start: now,
+            duration: 0.,
+        }));
+        return;
+    }
+
+    let delay = |per_tile: f32, from: Coordinates, to: Coordinates| {
+        let (dx, dy) = (from.x as f32 - to.x as f32, from.y as f32 - to.y as f32);
+        (dx.hypot(dy) * per_tile).min(options.max_delay) as f64
+    };
+    let mut scheduled = Vec::with_capacity(changed.len());
+    for TileChange {
+        coordinates,
+        origin,
+    } in changed
+    {
+        let (kind, start, duration) = match board.tile_sprite(coordinates) {
+            TileSprite::Revealed(_) => (
+                TweenKind::Reveal,
+                now + delay(options.cascade_delay, origin, coordinates),
+                options.reveal,
+            ),
+            TileSprite::Flag => (TweenKind::Flag, now, options.flag),
+            TileSprite::Mine | TileSprite::Exploded | TileSprite::WrongFlag => (
+                TweenKind::Explosion,
+                now + delay(options.explosion_delay, origin, coordinates),
+                options.explosion,
+            ),
+            TileSprite::Covered | TileSprite::Question => (TweenKind::Instant, now, 0.),
+        };
+        scheduled.push(TileTween {
+            coordinates,
+            kind,
+            start,
+            duration,
+        });
+    }
+    // A tile changing again takes over its pending tween
+    let rescheduled: HashSet<_> = scheduled.iter().map(|t| t.coordinates).collect();
+    tweens.0.retain(|t| !rescheduled.contains(&t.coordinates));
+    tweens.0.extend(scheduled);
+
+    if completed {
+        // Starts once the last tile uncovered flipped over
+        let start = tweens.0.iter().map(|t| t.start).fold(now, f64::max) + options.reveal as f64;
+        let center = Coordinates {
+            x: board.tile_map.width / 2,
+            y: board.tile_map.height / 2,
+        };
+        let celebration: Vec<_> = board
+            .coordinates()
+            .filter(|c| board.state(*c) == Some(TileState::Revealed))
+            .map(|c| TileTween {
+                coordinates: c,
+                kind: TweenKind::Celebration,
+                start: start + delay(options.cascade_delay, center, c),
+                duration: options.celebration,
+            })
+            .collect();
+        tweens.0.extend(celebration);
+    }
+}
@@ -0,0 +1,104 @@
+use crate::resources::CameraControls;
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+
+/// Mouse wheel pixels counting as one line step
+const PIXELS_PER_LINE: f32 = 100.;
+
+/// Puts the camera back on the whole board for every new game
+pub fn reset_camera(mut cameras: Query<&mut Transform, With<Camera2d>>) {
+    for mut transform in cameras.iter_mut() {
+        transform.translation.x = 0.;
+        transform.translation.y = 0.;
+        transform.scale = Vec3::ONE;
+    }
+}
+
+/// Zooms with the mouse wheel, keeping the point under the cursor in place
+pub fn zoom_camera(
+    windows: Res<Windows>,
+    camera_controls: Res<CameraControls>,
+    mut wheel_evr: EventReader<MouseWheel>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    let steps: f32 = wheel_evr
+        .iter()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+        })
+        .sum();
+    if steps == 0. {
+        return;
+    }
+    let window = windows.get_primary().unwrap();
+    let window_size = Vec2::new(window.width(), window.height());
+    let cursor = window
+        .cursor_position()
+        .map(|position| position - window_size / 2.)
+        .unwrap_or(Vec2::ZERO);
+
+    for mut transform in cameras.iter_mut() {
+        let scale = transform.scale.x;
+        let new_scale = (scale * (1. - camera_controls.zoom_step).powf(steps))
+            .clamp(camera_controls.min_zoom, camera_controls.max_zoom);
+        let offset = cursor * (scale - new_scale);
+        transform.translation.x += offset.x;
+        transform.translation.y += offset.y;
+        transform.scale = Vec3::new(new_scale, new_scale, 1.);
+    }
+}
+
+/// Pans by dragging with the pan button, or the left button while holding the pan key, and with
+/// the arrow keys while holding the keyboard pan modifier
+pub fn pan_camera(
+    windows: Res<Windows>,
+    time: Res<Time>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    camera_controls: Res<CameraControls>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let pan_key_pressed = keys.pressed(camera_controls.pan_key);
+    let dragging = buttons
+        .get_pressed()
+        .any(|button| camera_controls.pans_with(*button, pan_key_pressed));
+    let cursor = window.cursor_position();
+
+    let mut delta = Vec2::ZERO;
+    if dragging {
+        if let (Some(last), Some(cursor)) = (*last_cursor, cursor) {
+            delta += last - cursor;
+        }
+        *last_cursor = cursor;
+    } else {
+        *last_cursor = None;
+    }
+
+    if keys.pressed(camera_controls.keyboard_pan_modifier) {
+        let mut direction = Vec2::ZERO;
+        for (key, step) in [
+            (KeyCode::Left, -Vec2::X),
+            (KeyCode::Right, Vec2::X),
+            (KeyCode::Down, -Vec2::Y),
+            (KeyCode::Up, Vec2::Y),
+        ] {
+            if keys.pressed(key) {
+                direction += step;
+            }
+        }
+        delta += direction * camera_controls.keyboard_pan_speed * time.delta_seconds();
+    }
+
+    if delta == Vec2::ZERO {
+        return;
+    }
+    for mut transform in cameras.iter_mut() {
+        let scale = transform.scale.x;
+        transform.translation.x += delta.x * scale;
+        transform.translation.y += delta.y * scale;
+    }
+}
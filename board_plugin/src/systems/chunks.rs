@@ -0,0 +1,267 @@
+use crate::components::TileChunk;
+use crate::systems::animation::{TileTweens, TweenKind};
+use crate::{Board, BoardAssets, Coordinates};
+use bevy::prelude::*;
+use bevy::render::mesh::{Indices, PrimitiveTopology, VertexAttributeValues};
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+use bevy::utils::HashMap;
+
+/// Tiles along each side of a chunk mesh
+pub const CHUNK_SIZE: u16 = 32;
+
+/// UV corners of every atlas cell, bottom left then top right
+fn atlas_uvs(atlas: &TextureAtlas) -> Vec<[Vec2; 2]> {
+    atlas
+        .textures
+        .iter()
+        .map(|rect| {
+            [
+                Vec2::new(rect.min.x, rect.max.y) / atlas.size,
+                Vec2::new(rect.max.x, rect.min.y) / atlas.size,
+            ]
+        })
+        .collect()
+}
+
+/// Writes the four UVs of a tile quad
+fn set_tile_uvs(uvs: &mut [[f32; 2]], quad: usize, [min, max]: [Vec2; 2]) {
+    uvs[quad * 4] = [min.x, min.y];
+    uvs[quad * 4 + 1] = [max.x, min.y];
+    uvs[quad * 4 + 2] = [max.x, max.y];
+    uvs[quad * 4 + 3] = [min.x, max.y];
+}
+
+fn chunk_mesh(board: &Board, chunk: &TileChunk, padding: f32, uvs_by_sprite: &[[Vec2; 2]]) -> Mesh {
+    let size = board.tile_size;
+    let quads = chunk.width as usize * chunk.height as usize;
+    let mut positions = Vec::with_capacity(quads * 4);
+    let mut uvs = vec![[0.; 2]; quads * 4];
+    let mut indices = Vec::with_capacity(quads * 6);
+    for y in 0..chunk.height {
+        for x in 0..chunk.width {
+            let coordinates = chunk_tile(chunk, x, y);
+            let quad = positions.len() / 4;
+            let min = Vec2::new(x as f32, y as f32) * size + padding / 2.;
+            let max = min + size - padding;
+            positions.extend([
+                [min.x, min.y, 0.],
+                [max.x, min.y, 0.],
+                [max.x, max.y, 0.],
+                [min.x, max.y, 0.],
+            ]);
+            let index = board.tile_sprite(coordinates).index();
+            set_tile_uvs(&mut uvs, quad, uvs_by_sprite[index]);
+            let first = quad as u32 * 4;
+            indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0., 0., 1.]; quads * 4]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+fn chunk_tile(chunk: &TileChunk, x: u16, y: u16) -> Coordinates {
+    Coordinates {
+        x: chunk.x * CHUNK_SIZE + x,
+        y: chunk.y * CHUNK_SIZE + y,
+    }
+}
+
+/// Spawns the chunk meshes of a board, all sharing one material drawing the tile atlas
+pub(crate) fn spawn_tile_chunks(
+    parent: &mut ChildBuilder,
+    board: &Board,
+    padding: f32,
+    atlas: &TextureAtlas,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<ColorMaterial>,
+) {
+    let uvs = atlas_uvs(atlas);
+    let material = materials.add(ColorMaterial::from(atlas.texture.clone()));
+    let (width, height) = (board.tile_map.width, board.tile_map.height);
+    for y in 0..height.div_ceil(CHUNK_SIZE) {
+        for x in 0..width.div_ceil(CHUNK_SIZE) {
+            let chunk = TileChunk {
+                x,
+                y,
+                width: CHUNK_SIZE.min(width - x * CHUNK_SIZE),
+                height: CHUNK_SIZE.min(height - y * CHUNK_SIZE),
+            };
+            let origin = chunk_tile(&chunk, 0, 0);
+            parent
+                .spawn_bundle(MaterialMesh2dBundle {
+                    mesh: Mesh2dHandle(meshes.add(chunk_mesh(board, &chunk, padding, &uvs))),
+                    material: material.clone(),
+                    transform: Transform::from_xyz(
+                        origin.x as f32 * board.tile_size,
+                        origin.y as f32 * board.tile_size,
+                        1.,
+                    ),
+                    ..Default::default()
+                })
+                .insert(Name::new(format!("Tile chunk ({}, {})", x, y)))
+                .insert(chunk);
+        }
+    }
+}
+
+/// Patches the UVs of the tiles that changed, in their chunk mesh only. Chunks can't scale
+/// single tiles, so tweens only delay the change to the moment it shows
+pub fn update_tile_chunks(
+    board: Res<Board>,
+    time: Res<Time>,
+    mut tweens: ResMut<TileTweens>,
+    board_assets: Res<BoardAssets>,
+    atlases: Res<Assets<TextureAtlas>>,
+    chunks: Query<(&TileChunk, &Mesh2dHandle)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    if chunks.is_empty() {
+        return;
+    }
+    let now = time.seconds_since_startup();
+    let mut changed = Vec::new();
+    tweens.0.retain(|tween| match tween.progress(now) {
+        Some(progress) if tween.shows_change(progress) => {
+            if tween.kind != TweenKind::Celebration {
+                changed.push(tween.coordinates);
+            }
+            false
+        }
+        _ => true,
+    });
+    if changed.is_empty() {
+        return;
+    }
+    let uvs_by_sprite = match atlases.get(&board_assets.tile_atlas) {
+        Some(atlas) => atlas_uvs(atlas),
+        None => return,
+    };
+    let mut by_chunk: HashMap<(u16, u16), Vec<Coordinates>> = HashMap::default();
+    for coordinates in changed {
+        by_chunk
+            .entry((coordinates.x / CHUNK_SIZE, coordinates.y / CHUNK_SIZE))
+            .or_default()
+            .push(coordinates);
+    }
+    for (chunk, mesh) in chunks.iter() {
+        let tiles = match by_chunk.get(&(chunk.x, chunk.y)) {
+            Some(tiles) => tiles,
+            None => continue,
+        };
+        let uvs = match meshes
+            .get_mut(&mesh.0)
+            .and_then(|mesh| mesh.attribute_mut(Mesh::ATTRIBUTE_UV_0))
+        {
+            Some(VertexAttributeValues::Float32x2(uvs)) => uvs,
+            _ => continue,
+        };
+        for coordinates in tiles {
+            let quad = (coordinates.y % CHUNK_SIZE) as usize * chunk.width as usize
+                + (coordinates.x % CHUNK_SIZE) as usize;
+            let index = board.tile_sprite(*coordinates).index();
+            set_tile_uvs(uvs, quad, uvs_by_sprite[index]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::Bounds2;
+    use crate::resources::TileSprite;
+    use crate::systems::animation::TileTween;
+    use crate::TileMap;
+    use bevy::asset::AssetPlugin;
+    use bevy::core::CorePlugin;
+    use bevy::sprite::Rect;
+
+    /// Atlas of square cells in a row, only its UVs matter
+    fn atlas() -> TextureAtlas {
+        let mut atlas = TextureAtlas::new_empty(Handle::default(), Vec2::new(150., 10.));
+        for i in 0..TileSprite::COUNT {
+            let min = Vec2::new(i as f32 * 10., 0.);
+            atlas.add_texture(Rect {
+                min,
+                max: min + 10.,
+            });
+        }
+        atlas
+    }
+
+    fn uvs(app: &App, chunk: &Mesh2dHandle, quad: usize) -> Vec<[f32; 2]> {
+        let mesh = app.world.resource::<Assets<Mesh>>().get(&chunk.0).unwrap();
+        match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => uvs[quad * 4..quad * 4 + 4].to_vec(),
+            _ => panic!("chunk mesh without UVs"),
+        }
+    }
+
+    fn tile_uvs(atlas: &TextureAtlas, sprite: TileSprite) -> Vec<[f32; 2]> {
+        let mut uvs = vec![[0.; 2]; 4];
+        set_tile_uvs(&mut uvs, 0, atlas_uvs(atlas)[sprite.index()]);
+        uvs
+    }
+
+    #[test]
+    fn changed_tiles_are_patched_in_their_chunk_only() {
+        let mut app = App::new();
+        app.add_plugin(CorePlugin)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<TextureAtlas>()
+            .add_system(update_tile_chunks);
+
+        // Two chunks side by side, the right one narrower
+        let (width, height) = (CHUNK_SIZE + 8, 3);
+        let bounds = Bounds2 {
+            position: Vec2::ZERO,
+            size: Vec2::new(width as f32, height as f32),
+        };
+        let mut board = Board::new(TileMap::empty(width, height), bounds, 1., 0, Entity::from_raw(0));
+        let atlas = atlas();
+        let mut chunks = Vec::new();
+        for (x, chunk_width) in [(0, CHUNK_SIZE), (1, 8)] {
+            let chunk = TileChunk {
+                x,
+                y: 0,
+                width: chunk_width,
+                height,
+            };
+            let mesh = chunk_mesh(&board, &chunk, 0., &atlas_uvs(&atlas));
+            let mesh = Mesh2dHandle(app.world.resource_mut::<Assets<Mesh>>().add(mesh));
+            app.world.spawn().insert(chunk).insert(mesh.clone());
+            chunks.push(mesh);
+        }
+
+        let revealed = Coordinates {
+            x: CHUNK_SIZE + 1,
+            y: 1,
+        };
+        board.try_uncover_tile(revealed, revealed);
+        let tile_atlas = app.world.resource_mut::<Assets<TextureAtlas>>().add(atlas.clone());
+        app.insert_resource(board)
+            .insert_resource(BoardAssets {
+                tile_atlas,
+                ..BoardAssets::plain()
+            })
+            .insert_resource(TileTweens(vec![TileTween {
+                coordinates: revealed,
+                kind: TweenKind::Instant,
+                start: 0.,
+                duration: 0.,
+            }]));
+        app.update();
+
+        // Second row of the right chunk, 8 tiles wide
+        let revealed_uvs = tile_uvs(&atlas, TileSprite::Revealed(0));
+        assert_eq!(uvs(&app, &chunks[1], 8 + 1), revealed_uvs);
+        let covered_uvs = tile_uvs(&atlas, TileSprite::Covered);
+        assert_eq!(uvs(&app, &chunks[1], 8), covered_uvs);
+        assert_eq!(uvs(&app, &chunks[0], CHUNK_SIZE as usize + 1), covered_uvs);
+        assert!(app.world.resource::<TileTweens>().0.is_empty());
+    }
+}
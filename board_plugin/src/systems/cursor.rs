@@ -0,0 +1,146 @@
+use crate::components::TileCursorHighlight;
+use crate::events::{TileMarkEvent, TileTriggerEvent, UncoverAdjacentTilesEvent};
+use crate::resources::{BoardOptions, CameraControls, InputLock, TileCursor};
+use crate::{Board, Coordinates};
+use bevy::log;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+
+/// Moves the tile cursor to the tile under the mouse
+pub fn mouse_cursor(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    cameras: Query<&GlobalTransform, With<Camera2d>>,
+    mut cursor: ResMut<TileCursor>,
+    mut cursor_moved_evr: EventReader<CursorMoved>,
+) {
+    let window = windows.get_primary().unwrap();
+    let position = match cursor_moved_evr.iter().rfind(|e| e.id == window.id()) {
+        Some(event) => event.position,
+        None => return,
+    };
+    let camera = match cameras.get_single() {
+        Ok(camera) => camera,
+        Err(_) => return,
+    };
+    if let Some(coordinates) = board.mouse_position(window, camera, position) {
+        if cursor.0 != Some(coordinates) {
+            cursor.0 = Some(coordinates);
+        }
+    }
+}
+
+/// Moves the tile cursor with the arrow keys or hjkl, jumping to the board edge while holding
+/// control and to the next covered tile with tab, shift + tab going back. The keyboard pan
+/// modifier leaves the arrows and hjkl to the camera. Space reveals, F marks and D chords
+#[allow(clippy::too_many_arguments)]
+pub fn keyboard_cursor(
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    camera_controls: Res<CameraControls>,
+    input_lock: Option<Res<InputLock>>,
+    mut cursor: ResMut<TileCursor>,
+    mut space_panned: Local<bool>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut uncover_adjacent_tiles_ewr: EventWriter<UncoverAdjacentTilesEvent>,
+) {
+    if input_lock.is_some() {
+        return;
+    }
+    let width = board.tile_map.width;
+    let height = board.tile_map.height;
+    let current = cursor.get_or_center(&board.tile_map);
+
+    let mut target = None;
+    if !keys.pressed(camera_controls.keyboard_pan_modifier) {
+        let jump = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+        if keys.any_just_pressed([KeyCode::Left, KeyCode::H]) {
+            let x = if jump { 0 } else { current.x.saturating_sub(1) };
+            target = Some(Coordinates { x, ..current });
+        }
+        if keys.any_just_pressed([KeyCode::Right, KeyCode::L]) {
+            let x = if jump { width - 1 } else { (current.x + 1).min(width - 1) };
+            target = Some(Coordinates { x, ..current });
+        }
+        if keys.any_just_pressed([KeyCode::Down, KeyCode::J]) {
+            let y = if jump { 0 } else { current.y.saturating_sub(1) };
+            target = Some(Coordinates { y, ..current });
+        }
+        if keys.any_just_pressed([KeyCode::Up, KeyCode::K]) {
+            let y = if jump { height - 1 } else { (current.y + 1).min(height - 1) };
+            target = Some(Coordinates { y, ..current });
+        }
+    }
+    if keys.just_pressed(KeyCode::Tab) {
+        let backwards = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+        target = next_covered_tile(&board, current, backwards).or(target);
+    }
+    if let Some(target) = target {
+        cursor.0 = Some(target);
+    }
+
+    let coordinates = match cursor.0 {
+        Some(c) => c,
+        None => return,
+    };
+    // Space also pans the board while dragging, a release after a drag doesn't reveal
+    if keys.pressed(camera_controls.pan_key) && buttons.pressed(MouseButton::Left) {
+        *space_panned = true;
+    }
+    if keys.just_released(camera_controls.pan_key) {
+        if !*space_panned {
+            log::info!("Trying to uncover tile on {}", coordinates);
+            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+        }
+        *space_panned = false;
+    }
+    if keys.just_pressed(KeyCode::F) {
+        log::info!("Trying to mark tile on {}", coordinates);
+        tile_mark_ewr.send(TileMarkEvent(coordinates));
+    }
+    if keys.just_pressed(KeyCode::D) && board_options.is_none_or(|o| o.chording) {
+        log::info!("Trying to uncover adjacent tiles on {}", coordinates);
+        uncover_adjacent_tiles_ewr.send(UncoverAdjacentTilesEvent(coordinates));
+    }
+}
+
+/// Next tile that can still be uncovered, in row order from `from` and wrapping around the board
+fn next_covered_tile(board: &Board, from: Coordinates, backwards: bool) -> Option<Coordinates> {
+    let width = board.tile_map.width as usize;
+    let count = width * board.tile_map.height as usize;
+    let start = from.y as usize * width + from.x as usize;
+    (1..count)
+        .map(|offset| {
+            if backwards {
+                (start + count - offset) % count
+            } else {
+                (start + offset) % count
+            }
+        })
+        .map(|index| Coordinates {
+            x: (index % width) as u16,
+            y: (index / width) as u16,
+        })
+        .find(|coordinates| board.can_uncover(*coordinates))
+}
+
+/// Keeps the highlight sprite on the tile cursor
+pub fn cursor_highlight(
+    board: Res<Board>,
+    cursor: Res<TileCursor>,
+    mut highlights: Query<(&mut Transform, &mut Visibility), With<TileCursorHighlight>>,
+) {
+    if !cursor.is_changed() {
+        return;
+    }
+    for (mut transform, mut visibility) in highlights.iter_mut() {
+        visibility.is_visible = cursor.0.is_some();
+        if let Some(coordinates) = cursor.0 {
+            transform.translation.x = (coordinates.x as f32 + 0.5) * board.tile_size;
+            transform.translation.y = (coordinates.y as f32 + 0.5) * board.tile_size;
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::components::{Coordinates, DebugPanel, DebugTiles, TileChunk};
+use crate::raster::Canvas;
+use crate::resources::{BoardAssets, DebugOverlay};
+use crate::solver::{Deduction, Solver};
+use crate::{Board, TileMap};
+use bevy::log;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use std::fmt::Write;
+
+/// Longest side of the overlay texture in pixels
+const MAX_TEXTURE_SIZE: u32 = 4096;
+/// Pixels of the overlay texture per tile, less on big boards
+const TILE_PIXELS: u32 = 8;
+
+/// Board entity, covered tiles, flags and exploded tile the overlay was drawn for
+type DrawnBoard = (Entity, usize, usize, Option<Coordinates>);
+
+/// Pixels per tile of the overlay texture of a map, `None` when the map is too big for a pixel
+/// per tile
+fn tile_pixels(map: &TileMap) -> Option<u32> {
+    let side = map.width.max(map.height).max(1) as u32;
+    (side <= MAX_TEXTURE_SIZE).then(|| (MAX_TEXTURE_SIZE / side).min(TILE_PIXELS))
+}
+
+pub fn toggle_debug_overlay(keys: Res<Input<KeyCode>>, mut overlay: ResMut<DebugOverlay>) {
+    if keys.just_pressed(overlay.toggle_key) {
+        overlay.visible = !overlay.visible;
+    }
+}
+
+/// Spawns the overlay sprite over every new board and draws it again as the board changes
+pub fn update_debug_tiles(
+    mut commands: Commands,
+    board: Option<Res<Board>>,
+    mut overlay: ResMut<DebugOverlay>,
+    mut images: ResMut<Assets<Image>>,
+    mut tiles: Query<(&Handle<Image>, &mut Visibility), With<DebugTiles>>,
+    mut drawn: Local<Option<DrawnBoard>>,
+) {
+    let board = match board {
+        Some(b) => b,
+        None => return,
+    };
+    let map = &board.tile_map;
+    let tile_pixels = match tile_pixels(map) {
+        Some(pixels) => pixels,
+        None => {
+            if board.is_added() {
+                log::warn!("Board too big for the debug overlay texture, showing the panel only");
+            }
+            return;
+        }
+    };
+    if board.is_added() {
+        let size = Vec2::new(map.width as f32, map.height as f32) * board.tile_size;
+        let canvas = Canvas::new(map.width as u32 * tile_pixels, map.height as u32 * tile_pixels);
+        commands.entity(board.entity).with_children(|parent| {
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    texture: images.add(canvas.into_image()),
+                    visibility: Visibility {
+                        is_visible: overlay.visible,
+                    },
+                    transform: Transform::from_translation((size / 2.).extend(4.)),
+                    ..Default::default()
+                })
+                .insert(Name::new("Debug Tiles"))
+                .insert(DebugTiles);
+        });
+        *drawn = None;
+        return;
+    }
+
+    for (_, mut visibility) in tiles.iter_mut() {
+        if visibility.is_visible != overlay.visible {
+            visibility.is_visible = overlay.visible;
+        }
+    }
+    let state = Some((
+        board.entity,
+        board.covered_count(),
+        board.flag_count(),
+        board.exploded,
+    ));
+    let texture = match tiles.get_single() {
+        Ok((texture, _)) if overlay.visible && *drawn != state => texture.clone(),
+        _ => return,
+    };
+    *drawn = state;
+
+    let mut solver = Solver::for_board(&board);
+    overlay.probabilities = solver.probabilities();
+    overlay.deductions = solver
+        .deductions()
+        .into_iter()
+        .map(|deduction| match deduction {
+            Deduction::Safe(c) | Deduction::Bomb(c) => (c, deduction),
+        })
+        .collect();
+
+    let mut canvas = Canvas::new(map.width as u32 * tile_pixels, map.height as u32 * tile_pixels);
+    let pixels = tile_pixels as f32;
+    for coordinates in board.coordinates() {
+        let x = coordinates.x as i32 * tile_pixels as i32;
+        // Textures start at the top, boards at the bottom
+        let y = (map.height - 1 - coordinates.y) as i32 * tile_pixels as i32;
+        if board.is_covered(coordinates) {
+            let color = match overlay.deductions.get(&coordinates) {
+                Some(Deduction::Safe(_)) => Color::rgba(0., 1., 0., 0.5),
+                Some(Deduction::Bomb(_)) => Color::rgba(1., 0., 1., 0.5),
+                None => match overlay.probabilities.get(&coordinates) {
+                    Some(p) => Color::rgba(*p, 0.3 * (1. - p), 1. - p, 0.35),
+                    None => Color::NONE,
+                },
+            };
+            canvas.fill_rect(x, y, tile_pixels as i32, tile_pixels as i32, color);
+        }
+        if map.is_bomb_at(coordinates) && board.is_covered(coordinates) {
+            let center = Vec2::new(x as f32, y as f32) + pixels / 2.;
+            canvas.fill_circle(center, pixels * 0.25, Color::rgba(0., 0., 0., 0.6));
+            canvas.ring(center, pixels * 0.25, pixels * 0.08, Color::rgba(1., 1., 1., 0.6));
+        }
+    }
+    if let Some(image) = images.get_mut(&texture) {
+        *image = canvas.into_image();
+    }
+}
+
+/// Writes the board state, entity counts and the tile under the mouse in the overlay panel
+#[allow(clippy::too_many_arguments)]
+pub fn update_debug_panel(
+    mut commands: Commands,
+    board: Option<Res<Board>>,
+    board_assets: Option<Res<BoardAssets>>,
+    overlay: Res<DebugOverlay>,
+    windows: Res<Windows>,
+    cameras: Query<&GlobalTransform, With<Camera2d>>,
+    entities: Query<Entity>,
+    chunks: Query<(), With<TileChunk>>,
+    panels: Query<(Entity, &Children), With<DebugPanel>>,
+    mut texts: Query<&mut Text>,
+) {
+    let (board, board_assets) = match (board, board_assets) {
+        (Some(b), Some(a)) if overlay.visible => (b, a),
+        _ => {
+            for (panel, _) in panels.iter() {
+                commands.entity(panel).despawn_recursive();
+            }
+            return;
+        }
+    };
+    let mut text = match panels.get_single().map(|(_, children)| texts.get_mut(children[0])) {
+        Ok(Ok(text)) => text,
+        _ => {
+            if panels.is_empty() {
+                spawn_debug_panel(&mut commands, board_assets.bomb_counter_font.clone());
+            }
+            return;
+        }
+    };
+
+    let map = &board.tile_map;
+    let mut panel = String::new();
+    let _ = writeln!(
+        panel,
+        "Board {}x{}, {} bombs, {:?} numbers, seed {}",
+        map.width, map.height, map.bomb_count, map.number_rule, board.seed
+    );
+    let _ = writeln!(
+        panel,
+        "Covered {}, flags {}, exploded {:?}, completed {}",
+        board.covered_count(),
+        board.flag_count(),
+        board.exploded,
+        board.is_completed()
+    );
+    let _ = writeln!(
+        panel,
+        "Entities {}, tile chunks {}",
+        entities.iter().count(),
+        chunks.iter().count()
+    );
+    let _ = writeln!(
+        panel,
+        "Solver: {} safe, {} bombs proven",
+        overlay
+            .deductions
+            .values()
+            .filter(|d| matches!(d, Deduction::Safe(_)))
+            .count(),
+        overlay
+            .deductions
+            .values()
+            .filter(|d| matches!(d, Deduction::Bomb(_)))
+            .count()
+    );
+
+    let window = windows.get_primary().unwrap();
+    let hovered = match (window.cursor_position(), cameras.get_single()) {
+        (Some(position), Ok(camera)) => board.mouse_position(window, camera, position),
+        _ => None,
+    };
+    if let Some((coordinates, state)) = hovered.and_then(|c| Some((c, board.state(c)?))) {
+        let _ = write!(
+            panel,
+            "Tile {}: {:?}, {:?}",
+            coordinates,
+            state,
+            board.tile_map[coordinates.y as usize][coordinates.x as usize]
+        );
+        if let Some(p) = overlay.probabilities.get(&coordinates) {
+            let _ = write!(panel, ", {:.0}% bomb", p * 100.);
+        }
+    }
+    text.sections[0].value = panel;
+}
+
+fn spawn_debug_panel(commands: &mut Commands, font: Handle<Font>) {
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(8.),
+                    bottom: Val::Px(8.),
+                    ..Default::default()
+                },
+                padding: Rect::all(Val::Px(6.)),
+                ..Default::default()
+            },
+            color: Color::rgba(0., 0., 0., 0.7).into(),
+            ..Default::default()
+        })
+        .insert(Name::new("Debug Panel"))
+        .insert(DebugPanel)
+        .with_children(|parent| {
+            parent.spawn_bundle(TextBundle {
+                text: Text::with_section(
+                    "",
+                    TextStyle {
+                        font,
+                        font_size: 14.,
+                        color: Color::WHITE,
+                    },
+                    Default::default(),
+                ),
+                ..Default::default()
+            });
+        });
+}
@@ -0,0 +1,109 @@
+use crate::components::{PressedTile, TileHover};
+use crate::resources::{InputLock, TilePress, TileSprite};
+use crate::{Board, BoardAssets, Coordinates};
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+
+/// Tiles a chord presses in at most, its whole 3x3 neighborhood
+const PRESSED_TILES: usize = 9;
+
+/// The hover sprite, apart from the pressed tile sprites
+type HoverFilter = (With<TileHover>, Without<PressedTile>);
+
+/// The hover sprite and the pressed tile sprites
+type FeedbackFilter = Or<(With<TileHover>, With<PressedTile>)>;
+
+/// Spawns the hover sprite and the pressed tile sprites, hidden until used
+pub(crate) fn spawn_tile_feedback(
+    parent: &mut ChildBuilder,
+    board: &Board,
+    padding: f32,
+    board_assets: &BoardAssets,
+) {
+    let size = Vec2::splat(board.tile_size - padding);
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgba(1., 1., 1., 0.2),
+                custom_size: Some(size),
+                ..Default::default()
+            },
+            visibility: Visibility { is_visible: false },
+            transform: Transform::from_xyz(0., 0., 3.),
+            ..Default::default()
+        })
+        .insert(Name::new("Tile Hover"))
+        .insert(TileHover);
+    for _ in 0..PRESSED_TILES {
+        parent
+            .spawn_bundle(SpriteSheetBundle {
+                sprite: TextureAtlasSprite {
+                    index: TileSprite::Revealed(0).index(),
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                texture_atlas: board_assets.tile_atlas.clone(),
+                visibility: Visibility { is_visible: false },
+                transform: Transform::from_xyz(0., 0., 3.),
+                ..Default::default()
+            })
+            .insert(Name::new("Pressed Tile"))
+            .insert(PressedTile);
+    }
+}
+
+/// Lights up the covered tile under the mouse and draws the tiles of the current press pressed in
+pub fn tile_feedback(
+    windows: Res<Windows>,
+    board: Res<Board>,
+    tile_press: Res<TilePress>,
+    input_lock: Option<Res<InputLock>>,
+    cameras: Query<&GlobalTransform, With<Camera2d>>,
+    mut hovers: Query<(&mut Transform, &mut Visibility), HoverFilter>,
+    mut pressed_tiles: Query<(&mut Transform, &mut Visibility), With<PressedTile>>,
+) {
+    let window = windows.get_primary().unwrap();
+    let hovered = match (window.cursor_position(), cameras.get_single()) {
+        (Some(position), Ok(camera)) if input_lock.is_none() => {
+            board.mouse_position(window, camera, position)
+        }
+        _ => None,
+    };
+    let pressed = tile_press.pressed_tiles(&board);
+    let center = |coordinates: Coordinates| {
+        Vec2::new(coordinates.x as f32 + 0.5, coordinates.y as f32 + 0.5) * board.tile_size
+    };
+
+    let hovered = hovered.filter(|c| board.is_covered(*c) && !pressed.contains(c));
+    for (mut transform, mut visibility) in hovers.iter_mut() {
+        if visibility.is_visible != hovered.is_some() {
+            visibility.is_visible = hovered.is_some();
+        }
+        if let Some(coordinates) = hovered {
+            transform.translation = center(coordinates).extend(transform.translation.z);
+        }
+    }
+
+    let mut pressed = pressed.into_iter();
+    for (mut transform, mut visibility) in pressed_tiles.iter_mut() {
+        let coordinates = pressed.next();
+        if visibility.is_visible != coordinates.is_some() {
+            visibility.is_visible = coordinates.is_some();
+        }
+        if let Some(coordinates) = coordinates {
+            transform.translation = center(coordinates).extend(transform.translation.z);
+        }
+    }
+}
+
+/// Drops the press and hides its feedback while another state runs over the board, as the
+/// game end or pause screens
+pub fn clear_tile_feedback(
+    mut tile_press: ResMut<TilePress>,
+    mut sprites: Query<&mut Visibility, FeedbackFilter>,
+) {
+    tile_press.0 = None;
+    for mut visibility in sprites.iter_mut() {
+        visibility.is_visible = false;
+    }
+}
@@ -0,0 +1,156 @@
+use crate::events::{TileMarkEvent, TileTriggerEvent, UncoverAdjacentTilesEvent};
+use crate::resources::{BoardOptions, CameraControls, InputLock, TileCursor};
+use crate::Board;
+use bevy::log;
+use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
+use bevy::utils::HashMap;
+
+/// Stick deflection moving the cursor
+const STICK_THRESHOLD: f32 = 0.5;
+/// Seconds a stick is held before the cursor starts repeating
+const STICK_REPEAT_DELAY: f32 = 0.3;
+/// Seconds between repeated cursor moves while a stick is held
+const STICK_REPEAT_INTERVAL: f32 = 0.08;
+/// Keyboard pan speeds the right stick pans at
+const STICK_PAN_FACTOR: f32 = 1.5;
+
+/// Left stick direction held by a gamepad, with the seconds until it moves the cursor again
+#[derive(Debug, Default, Copy, Clone)]
+pub struct StickRepeat {
+    direction: (i8, i8),
+    cooldown: f32,
+}
+
+fn stick_direction(axes: &Axis<GamepadAxis>, gamepad: Gamepad) -> (i8, i8) {
+    let value = |axis| axes.get(GamepadAxis(gamepad, axis)).unwrap_or(0.);
+    let step = |v: f32| {
+        if v > STICK_THRESHOLD {
+            1
+        } else if v < -STICK_THRESHOLD {
+            -1
+        } else {
+            0
+        }
+    };
+    (
+        step(value(GamepadAxisType::LeftStickX)),
+        step(value(GamepadAxisType::LeftStickY)),
+    )
+}
+
+/// Moves the tile cursor with the left stick or the d-pad of any connected gamepad. South reveals,
+/// East marks and West chords
+#[allow(clippy::too_many_arguments)]
+pub fn gamepad_cursor(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    input_lock: Option<Res<InputLock>>,
+    mut cursor: ResMut<TileCursor>,
+    mut sticks: Local<HashMap<Gamepad, StickRepeat>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut uncover_adjacent_tiles_ewr: EventWriter<UncoverAdjacentTilesEvent>,
+) {
+    // Pads unplugged since the last frame leave no repeat behind
+    sticks.retain(|gamepad, _| gamepads.contains(gamepad));
+    if input_lock.is_some() {
+        return;
+    }
+    let chording = board_options.is_none_or(|o| o.chording);
+
+    for gamepad in gamepads.iter().copied() {
+        let pressed = |button| buttons.just_pressed(GamepadButton(gamepad, button));
+
+        let mut step = (0, 0);
+        for (button, direction) in [
+            (GamepadButtonType::DPadLeft, (-1, 0)),
+            (GamepadButtonType::DPadRight, (1, 0)),
+            (GamepadButtonType::DPadDown, (0, -1)),
+            (GamepadButtonType::DPadUp, (0, 1)),
+        ] {
+            if pressed(button) {
+                step = direction;
+            }
+        }
+
+        let direction = stick_direction(&axes, gamepad);
+        let stick = sticks.entry(gamepad).or_default();
+        if direction == (0, 0) {
+            *stick = StickRepeat::default();
+        } else if direction != stick.direction {
+            *stick = StickRepeat {
+                direction,
+                cooldown: STICK_REPEAT_DELAY,
+            };
+            step = direction;
+        } else {
+            stick.cooldown -= time.delta_seconds();
+            if stick.cooldown <= 0. {
+                stick.cooldown += STICK_REPEAT_INTERVAL;
+                step = direction;
+            }
+        }
+        if step != (0, 0) {
+            cursor.0 = Some(cursor.step(&board.tile_map, step));
+        }
+
+        let coordinates = cursor.get_or_center(&board.tile_map);
+        if pressed(GamepadButtonType::South) {
+            log::info!("{:?} trying to uncover tile on {}", gamepad, coordinates);
+            cursor.0 = Some(coordinates);
+            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+        }
+        if pressed(GamepadButtonType::East) {
+            log::info!("{:?} trying to mark tile on {}", gamepad, coordinates);
+            cursor.0 = Some(coordinates);
+            tile_mark_ewr.send(TileMarkEvent(coordinates));
+        }
+        if pressed(GamepadButtonType::West) && chording {
+            log::info!("{:?} trying to uncover adjacent tiles on {}", gamepad, coordinates);
+            cursor.0 = Some(coordinates);
+            uncover_adjacent_tiles_ewr.send(UncoverAdjacentTilesEvent(coordinates));
+        }
+    }
+}
+
+/// Zooms with the shoulder buttons and pans with the right stick of any connected gamepad
+pub fn gamepad_camera(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    camera_controls: Res<CameraControls>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    let mut zoom_steps = 0.;
+    let mut pan = Vec2::ZERO;
+    for gamepad in gamepads.iter().copied() {
+        if buttons.just_pressed(GamepadButton(gamepad, GamepadButtonType::RightTrigger)) {
+            zoom_steps += 1.;
+        }
+        if buttons.just_pressed(GamepadButton(gamepad, GamepadButtonType::LeftTrigger)) {
+            zoom_steps -= 1.;
+        }
+        let value = |axis| axes.get(GamepadAxis(gamepad, axis)).unwrap_or(0.);
+        pan += Vec2::new(
+            value(GamepadAxisType::RightStickX),
+            value(GamepadAxisType::RightStickY),
+        );
+    }
+    if zoom_steps == 0. && pan == Vec2::ZERO {
+        return;
+    }
+    let pan = pan * camera_controls.keyboard_pan_speed * STICK_PAN_FACTOR * time.delta_seconds();
+    for mut transform in cameras.iter_mut() {
+        let scale = (transform.scale.x * (1. - camera_controls.zoom_step).powf(zoom_steps))
+            .clamp(camera_controls.min_zoom, camera_controls.max_zoom);
+        transform.scale = Vec3::new(scale, scale, 1.);
+        transform.translation.x += pan.x * scale;
+        transform.translation.y += pan.y * scale;
+    }
+}
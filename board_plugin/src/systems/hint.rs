@@ -0,0 +1,25 @@
+use crate::events::{HintRequestEvent, HintUsedEvent};
+use crate::solver::Solver;
+use crate::Board;
+use bevy::log;
+use bevy::prelude::*;
+
+pub fn hint_handler(
+    mut board: ResMut<Board>,
+    mut hint_request_evr: EventReader<HintRequestEvent>,
+    mut hint_used_ewr: EventWriter<HintUsedEvent>,
+) {
+    if hint_request_evr.iter().count() == 0 {
+        return;
+    }
+
+    let hint = Solver::for_board(&board).next_safe_tile();
+    let hint = hint.filter(|coords| board.request_uncover(*coords));
+    match hint {
+        Some(coords) => {
+            log::info!("Hint: {} is safe", coords);
+            hint_used_ewr.send(HintUsedEvent(coords));
+        }
+        None => log::info!("No tile can be proven safe"),
+    }
+}
@@ -1,42 +1,105 @@
 use crate::Board;
+use crate::resources::{BoardOptions, CameraControls, InputLock, Press, TilePress};
 use crate::events::{TileTriggerEvent, TileMarkEvent, UncoverAdjacentTilesEvent};
 
 use bevy::input::{mouse::MouseButtonInput, ElementState};
 use bevy::log;
 use bevy::prelude::*;
+use bevy::render::camera::Camera2d;
 
+/// Tracks the mouse button held on a tile and sends its action once released over that same tile,
+/// dragging off the tile cancels it
+#[allow(clippy::too_many_arguments)]
 pub fn input_handling(
     windows: Res<Windows>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    cameras: Query<&GlobalTransform, With<Camera2d>>,
     board: Res<Board>,
+    camera_controls: Res<CameraControls>,
+    board_options: Option<Res<BoardOptions>>,
+    input_lock: Option<Res<InputLock>>,
+    mut tile_press: ResMut<TilePress>,
     mut button_evr: EventReader<MouseButtonInput>,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
     mut tile_mark_ewr: EventWriter<TileMarkEvent>,
     mut uncover_adjacent_tiles_ewr: EventWriter<UncoverAdjacentTilesEvent>,
 ) {
+    if input_lock.is_some() {
+        button_evr.iter().for_each(drop);
+        if tile_press.0.is_some() {
+            tile_press.0 = None;
+        }
+        return;
+    }
     let window = windows.get_primary().unwrap();
+    let chording = board_options.is_none_or(|o| o.chording);
+    let camera = match cameras.get_single() {
+        Ok(camera) => camera,
+        Err(_) => {
+            button_evr.iter().for_each(drop);
+            return;
+        }
+    };
+    let pan_key_pressed = keys.pressed(camera_controls.pan_key);
+    let hovered = window
+        .cursor_position()
+        .and_then(|pos| board.mouse_position(window, camera, pos));
+
+    if let Some(press) = tile_press.0 {
+        let over = hovered == Some(press.coordinates);
+        // Released while the board wasn't running
+        if !buttons.pressed(press.button) && !buttons.just_released(press.button) {
+            tile_press.0 = None;
+        } else if press.over != over {
+            tile_press.0 = Some(Press { over, ..press });
+        }
+    }
 
     for event in button_evr.iter() {
-        if let ElementState::Pressed = event.state {
-            let position = window.cursor_position();
-            if let Some(pos) = position {
-                log::trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
-                if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            log::info!("Trying to uncover tile on {}", coordinates);
-                            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
-                        }
-                        MouseButton::Right => {
-                            log::info!("Trying to mark tile on {}", coordinates);
-                            tile_mark_ewr.send(TileMarkEvent(coordinates));
-                        }
-                        MouseButton::Middle => {
-                            log::info!("Trying to uncover adjacent tiles on {}", coordinates);
-                            uncover_adjacent_tiles_ewr.send(UncoverAdjacentTilesEvent(coordinates));
-                        }
-                        _ => (),
+        match event.state {
+            ElementState::Pressed => {
+                // A second button held during a press doesn't start another action
+                if tile_press.0.is_some()
+                    || camera_controls.pans_with(event.button, pan_key_pressed)
+                    || event.button == MouseButton::Middle && !chording
+                {
+                    continue;
+                }
+                if let Some(coordinates) = hovered {
+                    log::trace!("Mouse button pressed: {:?} on {}", event.button, coordinates);
+                    tile_press.0 = Some(Press {
+                        button: event.button,
+                        coordinates,
+                        over: true,
+                    });
+                }
+            }
+            ElementState::Released => {
+                let press = match tile_press.0 {
+                    Some(press) if press.button == event.button => press,
+                    _ => continue,
+                };
+                tile_press.0 = None;
+                if hovered != Some(press.coordinates) {
+                    log::trace!("Mouse button released off {}, cancelled", press.coordinates);
+                    continue;
+                }
+                let coordinates = press.coordinates;
+                match press.button {
+                    MouseButton::Left => {
+                        log::info!("Trying to uncover tile on {}", coordinates);
+                        tile_trigger_ewr.send(TileTriggerEvent(coordinates));
+                    }
+                    MouseButton::Right => {
+                        log::info!("Trying to mark tile on {}", coordinates);
+                        tile_mark_ewr.send(TileMarkEvent(coordinates));
+                    }
+                    MouseButton::Middle => {
+                        log::info!("Trying to uncover adjacent tiles on {}", coordinates);
+                        uncover_adjacent_tiles_ewr.send(UncoverAdjacentTilesEvent(coordinates));
                     }
+                    _ => (),
                 }
             }
         }
@@ -0,0 +1,31 @@
+use crate::{Board, BoardOptions};
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+
+/// Fits the board to the window again when it is resized, by scaling the board entity so the
+/// tiles keep their local size
+pub fn resize_board(
+    mut board: ResMut<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    windows: Res<Windows>,
+    mut window_resized_evr: EventReader<WindowResized>,
+    mut transforms: Query<&mut Transform>,
+) {
+    let primary = windows.get_primary().unwrap();
+    if !window_resized_evr.iter().any(|e| e.id == primary.id()) {
+        return;
+    }
+    let options = board_options.map(|o| o.clone()).unwrap_or_default();
+    let map_size = (board.tile_map.width, board.tile_map.height);
+    let (tile_size, position, size) =
+        options.layout(Vec2::new(primary.width(), primary.height()), map_size);
+
+    if let Ok(mut transform) = transforms.get_mut(board.entity) {
+        transform.translation = position;
+        let scale = tile_size / board.tile_size;
+        transform.scale = Vec3::new(scale, scale, 1.);
+    }
+    board.bounds.position = position.xy();
+    board.bounds.size = size;
+}
@@ -1,42 +1,29 @@
-use crate::{Board, BoardAssets, TileMarkEvent};
-use bevy::log;
+use crate::resources::TileState;
+use crate::events::TileMarkedEvent;
+use crate::{Board, BoardOptions, TileMarkEvent};
 use bevy::prelude::*;
 
 pub fn mark_tiles(
-    mut commands: Commands,
     mut board: ResMut<Board>,
-    board_assets: Res<BoardAssets>,
+    board_options: Option<Res<BoardOptions>>,
     mut tile_mark_event_ewr: EventReader<TileMarkEvent>,
-    query: Query<&Children>,
+    mut tile_marked_event_wr: EventWriter<TileMarkedEvent>,
 ) {
+    let question_marks = board_options.is_some_and(|o| o.question_marks);
     for event in tile_mark_event_ewr.iter() {
-        if let Some((entity, mark)) = board.try_toggle_mark(&event.0) {
-            if mark {
-                commands.entity(entity).with_children(|parent| {
-                    parent.spawn_bundle(SpriteBundle {
-                        texture: board_assets.flag_material.texture.clone(),
-                        sprite: Sprite {
-                            custom_size: Some(Vec2::splat(board.tile_size)),
-                            color: board_assets.flag_material.color,
-                            ..Default::default()
-                        },
-                        transform: Transform::from_xyz(0., 0., 1.),
-                        ..Default::default()
-                    })
-                    .insert(Name::new("Flag"));
-                });
-            } else {
-                let children = match query.get(entity) {
-                    Ok(c) => c,
-                    Err(e) => {
-                        log::error!("Failed to retrieve flag entity components: {}", e);
-                        continue;
-                    }
-                };
-                for child in children.iter() {
-                    commands.entity(*child).despawn_recursive();
-                }
-            }
+        let coords = event.0;
+        let state = match board.state(coords) {
+            Some(state) => state,
+            None => continue,
+        };
+        let cycles_to_question = question_marks && state == TileState::Flagged;
+        let changed = if cycles_to_question || state == TileState::Questioned {
+            board.try_toggle_question(coords)
+        } else {
+            board.try_toggle_mark(coords)
+        };
+        if let (Some(_), Some(state)) = (changed, board.state(coords)) {
+            tile_marked_event_wr.send(TileMarkedEvent(coords, state));
         }
     }
 }
@@ -1,3 +1,15 @@
 pub mod input;
 pub mod uncover;
-pub mod mark;
\ No newline at end of file
+pub mod mark;
+pub mod hint;
+pub mod layout;
+pub mod camera;
+pub mod cursor;
+pub mod gamepad;
+pub mod theme;
+pub mod sprites;
+pub mod chunks;
+pub mod animation;
+pub mod feedback;
+#[cfg(all(feature = "debug", debug_assertions))]
+pub mod debug_overlay;
@@ -0,0 +1,84 @@
+use crate::components::{Bomb, BombNeighbor, Coordinates, TileSprites};
+use crate::resources::tile::Tile;
+use crate::resources::TileSprite;
+use crate::systems::animation::TileTweens;
+use crate::{Board, BoardAssets};
+use bevy::prelude::*;
+
+/// Spawns a sprite per tile, returning them row by row from the bottom
+pub(crate) fn spawn_tile_sprites(
+    parent: &mut ChildBuilder,
+    board: &Board,
+    padding: f32,
+    board_assets: &BoardAssets,
+) -> Vec<Entity> {
+    let size = board.tile_size;
+    board
+        .coordinates()
+        .map(|coordinates| {
+            let mut cmd = parent.spawn_bundle(SpriteSheetBundle {
+                sprite: TextureAtlasSprite {
+                    index: board.tile_sprite(coordinates).index(),
+                    custom_size: Some(Vec2::splat(size - padding)),
+                    ..Default::default()
+                },
+                texture_atlas: board_assets.tile_atlas.clone(),
+                transform: Transform::from_xyz(
+                    (coordinates.x as f32 * size) + (size / 2.),
+                    (coordinates.y as f32 * size) + (size / 2.),
+                    1.,
+                ),
+                ..Default::default()
+            });
+            cmd.insert(Name::new(format!("Tile {}", coordinates)))
+                .insert(coordinates);
+            match board.tile_map[coordinates.y as usize][coordinates.x as usize] {
+                Tile::Bomb => {
+                    cmd.insert(Bomb);
+                }
+                Tile::BombNeighbor(count) => {
+                    cmd.insert(BombNeighbor { count });
+                }
+                Tile::Empty => (),
+            }
+            cmd.id()
+        })
+        .collect()
+}
+
+/// Plays the tile tweens on the tile sprites, pointing them to their new atlas cell
+pub fn update_tile_sprites(
+    board: Res<Board>,
+    time: Res<Time>,
+    mut tweens: ResMut<TileTweens>,
+    grids: Query<&TileSprites>,
+    mut sprites: Query<(&mut TextureAtlasSprite, &mut Transform), With<Coordinates>>,
+) {
+    let grid = match grids.get_single() {
+        Ok(grid) => grid,
+        Err(_) => return,
+    };
+    let now = time.seconds_since_startup();
+    let width = board.tile_map.width as usize;
+    tweens.0.retain(|tween| {
+        let progress = match tween.progress(now) {
+            Some(progress) => progress,
+            None => return true,
+        };
+        let coordinates = tween.coordinates;
+        let entity = grid.0[coordinates.y as usize * width + coordinates.x as usize];
+        if let Ok((mut sprite, mut transform)) = sprites.get_mut(entity) {
+            sprite.index = if tween.shows_change(progress) {
+                board.tile_sprite(coordinates).index()
+            } else {
+                TileSprite::Covered.index()
+            };
+            let (scale, color) = tween.look(progress);
+            transform.scale = scale.extend(1.);
+            // Tiles growing over their neighbors are drawn above them
+            transform.translation.z = if progress < 1. { 2. } else { 1. };
+            sprite.color = color;
+        }
+        progress < 1.
+    });
+}
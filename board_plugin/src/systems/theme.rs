@@ -0,0 +1,105 @@
+use crate::components::{Coordinates, PressedTile, Themed, TileChunk};
+use crate::resources::{build_tile_atlas, ActiveTheme, BoardAssets, NumberStyle};
+use bevy::asset::LoadState;
+use bevy::log;
+use bevy::prelude::*;
+
+/// Tiles and pressed tile sprites, drawn from the tile atlas
+type TileAtlasFilter = Or<(With<Coordinates>, With<PressedTile>)>;
+
+/// Inserts the active theme as the `BoardAssets` resource once it, its textures and its font are
+/// loaded, again when it is switched, its files edited or the `NumberStyle` changed, and restyles
+/// the board in place so the game goes on
+#[allow(clippy::too_many_arguments)]
+pub fn apply_theme(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_theme: Option<Res<ActiveTheme>>,
+    number_style: Option<Res<NumberStyle>>,
+    themes: Res<Assets<BoardAssets>>,
+    mut images: ResMut<Assets<Image>>,
+    fonts: Res<Assets<Font>>,
+    mut atlases: ResMut<Assets<TextureAtlas>>,
+    mut theme_evr: EventReader<AssetEvent<BoardAssets>>,
+    mut image_evr: EventReader<AssetEvent<Image>>,
+    mut pending: Local<bool>,
+    mut parts: Query<(&Themed, &mut Sprite, &mut Handle<Image>)>,
+    mut tiles: Query<&mut Handle<TextureAtlas>, TileAtlasFilter>,
+    chunks: Query<&Handle<ColorMaterial>, With<TileChunk>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let active_theme = match active_theme {
+        Some(t) => t,
+        None => return,
+    };
+    let theme = themes.get(&active_theme.0);
+    let textures = theme
+        .map(|theme| {
+            [
+                &theme.board_material,
+                &theme.tile_material,
+                &theme.covered_tile_material,
+                &theme.flag_material,
+                &theme.bomb_material,
+                &theme.cursor_material,
+            ]
+            .into_iter()
+            .map(|material| material.texture.clone())
+            .chain(theme.glyph_sheet.iter().map(|sheet| sheet.texture.clone()))
+            .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let theme_changed = theme_evr.iter().any(|event| match event {
+        AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+            *handle == active_theme.0
+        }
+        AssetEvent::Removed { .. } => false,
+    });
+    let texture_edited = image_evr.iter().any(|event| match event {
+        AssetEvent::Modified { handle } => textures.contains(handle),
+        _ => false,
+    });
+    let style_changed = number_style.as_ref().is_some_and(|style| style.is_changed());
+    if theme_changed || texture_edited || style_changed || active_theme.is_changed() {
+        *pending = true;
+    }
+    let theme = match theme {
+        Some(t) if *pending => t,
+        // Applied by its `Created` event
+        _ => return,
+    };
+    // Textures that failed to load are drawn as plain colors, numbers fall back to bitmaps
+    let textures_loading = textures.iter().any(|texture| {
+        images.get(texture).is_none() && asset_server.get_load_state(texture) != LoadState::Failed
+    });
+    let font_loading = fonts.get(&theme.bomb_counter_font).is_none()
+        && asset_server.get_load_state(&theme.bomb_counter_font) != LoadState::Failed;
+    if textures_loading || font_loading {
+        return;
+    }
+    *pending = false;
+
+    log::info!("Applying theme {}", theme.label);
+    let mut theme = theme.clone();
+    let style = number_style.map(|s| s.clone()).unwrap_or_default();
+    theme.tile_atlas = atlases.add(build_tile_atlas(&theme, &style, &mut images, &fonts));
+
+    for (part, mut sprite, mut texture) in parts.iter_mut() {
+        let material = theme.material(*part);
+        sprite.color = material.color;
+        *texture = material.texture.clone();
+    }
+    for mut atlas in tiles.iter_mut() {
+        *atlas = theme.tile_atlas.clone();
+    }
+    // Chunk meshes keep their UVs, the atlas layout doesn't change between themes
+    if let Some(atlas) = atlases.get(&theme.tile_atlas) {
+        for material in chunks.iter() {
+            if let Some(material) = materials.get_mut(material) {
+                material.texture = Some(atlas.texture.clone());
+            }
+        }
+    }
+    commands.insert_resource(theme);
+}
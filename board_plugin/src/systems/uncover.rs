@@ -1,53 +1,49 @@
-use crate::events::UncoverAdjacentTilesEvent;
-use crate::{
-    Board, BoardCompletedEvent, Bomb, BombExplosionEvent, BombNeighbor, Coordinates,
-    TileTriggerEvent, Uncover,
-};
+use crate::events::{OpeningClearedEvent, TilesUncoveredEvent, UncoverAdjacentTilesEvent};
+use crate::{Board, BoardCompletedEvent, BombExplosionEvent, Coordinates, TileTriggerEvent};
+use crate::resources::tile::Tile;
 use bevy::log;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 
 pub fn trigger_event_handler(
-    mut commands: Commands,
-    board: Res<Board>,
+    mut board: ResMut<Board>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
     mut uncover_adjacent_tiles_evr: EventReader<UncoverAdjacentTilesEvent>,
 ) {
     for trigger_event in tile_trigger_evr.iter() {
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
-        }
+        board.request_uncover(trigger_event.0);
     }
     for trigger_event in uncover_adjacent_tiles_evr.iter() {
-        for tile in board.tile_map.safe_square_at(trigger_event.0) {
-            if let Some(entity) = board.tile_to_uncover(&tile) {
-                commands.entity(*entity).insert(Uncover);
-            }
+        let tiles: Vec<_> = board.neighbors(trigger_event.0).collect();
+        for tile in tiles {
+            board.request_uncover(tile);
         }
     }
 }
 
+/// Uncovers the requested tiles, flooding through openings
 pub fn uncover_tiles(
-    mut commands: Commands,
     mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
     mut board_completed_event_wr: EventWriter<BoardCompletedEvent>,
     mut bomb_explosion_event_wr: EventWriter<BombExplosionEvent>,
+    mut opening_cleared_event_wr: EventWriter<OpeningClearedEvent>,
+    mut tiles_uncovered_event_wr: EventWriter<TilesUncoveredEvent>,
 ) {
-    for (entity, parent) in children.iter() {
-        commands.entity(entity).despawn_recursive();
-
-        let (coords, bomb, bomb_counter) = match parents.get(parent.0) {
-            Ok(v) => v,
-            Err(e) => {
-                log::error!("{}", e);
-                continue;
-            }
-        };
-
-        match board.try_uncover_tile(coords) {
-            None => log::debug!("Tried to uncover an already uncovered tile"),
-            Some(e) => log::debug!("Uncovered tile {} (entity: {:?})", coords, e),
+    // Cascading tiles keep the tile the player uncovered, their animation spreads from it
+    let mut to_uncover: Vec<_> = board
+        .take_uncover_requests()
+        .into_iter()
+        .map(|coords| (coords, coords))
+        .collect();
+    let mut uncovered: HashMap<Coordinates, usize> = HashMap::default();
+    while let Some((coords, origin)) = to_uncover.pop() {
+        if !board.try_uncover_tile(coords, origin) {
+            log::debug!("Tried to uncover an already uncovered tile");
+            continue;
+        }
+        log::debug!("Uncovered tile {}", coords);
+        if !board.tile_map.is_bomb_at(coords) {
+            *uncovered.entry(origin).or_default() += 1;
         }
 
         if board.is_completed() {
@@ -55,13 +51,29 @@ pub fn uncover_tiles(
             board_completed_event_wr.send(BoardCompletedEvent);
         }
 
-        if bomb.is_some() {
-            log::info!("Boom !");
-            bomb_explosion_event_wr.send(BombExplosionEvent);
-        } else if bomb_counter.is_none() {
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
+        match board.tile_map[coords.y as usize][coords.x as usize] {
+            Tile::Bomb => {
+                log::info!("Boom !");
+                board.explode(coords);
+                bomb_explosion_event_wr.send(BombExplosionEvent);
+                // Nothing else uncovers once the game is lost
+                break;
             }
+            Tile::Empty => {
+                if board.clear_opening(&coords) {
+                    opening_cleared_event_wr.send(OpeningClearedEvent(coords));
+                }
+                to_uncover.extend(
+                    board
+                        .adjacent_covered_tiles(coords)
+                        .into_iter()
+                        .map(|c| (c, origin)),
+                );
+            }
+            Tile::BombNeighbor(_) => (),
         }
     }
+    for (origin, count) in uncovered {
+        tiles_uncovered_event_wr.send(TilesUncoveredEvent { origin, count });
+    }
 }
@@ -0,0 +1,293 @@
+//! Command line arguments of the client, used to launch a given board from scripts and bug
+//! reports without going through the menu.
+
+use crate::resources::{CustomGameError, Difficulty, Replay, ReplayError};
+use board_plugin::resources::{BoardOptions, Puzzle, PuzzleError};
+use std::fmt::{self, Display, Formatter};
+use std::path::PathBuf;
+
+pub const USAGE: &str = "Usage: client [OPTIONS]
+
+Options:
+    --width <tiles>      Board width
+    --height <tiles>     Board height
+    --mines <count>      Bomb count
+    --preset <name>      beginner, intermediate or expert
+    --seed <seed>        Seed of the bomb layout
+    --replay <file>      Plays back a replay, like the last_replay.ron of the data directory
+    --puzzle <file>      Plays a puzzle from a RON file
+    --theme <name>       Board theme
+    --help               Prints this message";
+
+#[derive(Debug)]
+pub enum CliError {
+    MissingValue(String),
+    InvalidValue { flag: String, value: String },
+    UnknownArgument(String),
+    UnknownPreset(String),
+    /// `--replay` given with a board, which the replay already sets
+    ReplayWithBoard,
+    Board(CustomGameError),
+    Read { path: PathBuf, error: String },
+    Puzzle(PuzzleError),
+    Replay(ReplayError),
+}
+
+impl Display for CliError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::MissingValue(flag) => write!(f, "{} needs a value", flag),
+            CliError::InvalidValue { flag, value } => {
+                write!(f, "invalid value {} for {}", value, flag)
+            }
+            CliError::UnknownArgument(arg) => write!(f, "unknown argument {}", arg),
+            CliError::UnknownPreset(name) => write!(f, "unknown preset {}", name),
+            CliError::ReplayWithBoard => {
+                write!(f, "--replay can't be combined with board or puzzle options")
+            }
+            CliError::Board(e) => write!(f, "{}", e),
+            CliError::Read { path, error } => {
+                write!(f, "failed to read {}: {}", path.display(), error)
+            }
+            CliError::Puzzle(e) => write!(f, "invalid puzzle: {}", e),
+            CliError::Replay(e) => write!(f, "invalid replay: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// What the client starts with, the menu is skipped when a board or replay is given
+#[derive(Debug, Default)]
+pub struct Launch {
+    pub board: Option<BoardOptions>,
+    pub puzzle: Option<Puzzle>,
+    pub replay: Option<Replay>,
+    pub theme: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct CliArgs {
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    pub mines: Option<u16>,
+    pub preset: Option<Difficulty>,
+    pub seed: Option<u64>,
+    pub replay: Option<PathBuf>,
+    pub puzzle: Option<PathBuf>,
+    pub theme: Option<String>,
+    pub help: bool,
+}
+
+fn value<T: std::str::FromStr>(
+    flag: &str,
+    args: &mut impl Iterator<Item = String>,
+) -> Result<T, CliError> {
+    let value = args
+        .next()
+        .ok_or_else(|| CliError::MissingValue(flag.to_string()))?;
+    value.parse().map_err(|_| CliError::InvalidValue {
+        flag: flag.to_string(),
+        value,
+    })
+}
+
+impl CliArgs {
+    /// Parses the arguments following the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, CliError> {
+        let mut cli = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--width" => cli.width = Some(value(&arg, &mut args)?),
+                "--height" => cli.height = Some(value(&arg, &mut args)?),
+                "--mines" => cli.mines = Some(value(&arg, &mut args)?),
+                "--seed" => cli.seed = Some(value(&arg, &mut args)?),
+                "--preset" => {
+                    let name: String = value(&arg, &mut args)?;
+                    let preset =
+                        Difficulty::preset(&name).ok_or(CliError::UnknownPreset(name))?;
+                    cli.preset = Some(preset);
+                }
+                "--replay" => cli.replay = Some(value(&arg, &mut args)?),
+                "--puzzle" => cli.puzzle = Some(value(&arg, &mut args)?),
+                "--theme" => cli.theme = Some(value(&arg, &mut args)?),
+                "--help" | "-h" => cli.help = true,
+                _ => return Err(CliError::UnknownArgument(arg)),
+            }
+        }
+        Ok(cli)
+    }
+
+    /// Checks the arguments and reads the given files, `options` being the options of random
+    /// boards from the settings
+    pub fn resolve(self, options: &BoardOptions) -> Result<Launch, CliError> {
+        let gives_board = self.width.is_some()
+            || self.height.is_some()
+            || self.mines.is_some()
+            || self.preset.is_some()
+            || self.seed.is_some()
+            || self.puzzle.is_some();
+        if gives_board && self.replay.is_some() {
+            return Err(CliError::ReplayWithBoard);
+        }
+        let board = if gives_board {
+            let mut options = options.clone();
+            self.apply(&mut options)?;
+            Some(options)
+        } else {
+            None
+        };
+        Ok(Launch {
+            board,
+            puzzle: self.load_puzzle()?,
+            replay: self.load_replay()?,
+            theme: self.theme,
+        })
+    }
+
+    /// Applies the board size, bomb count and seed to `options`, starting from the preset if
+    /// there is one
+    fn apply(&self, options: &mut BoardOptions) -> Result<(), CliError> {
+        if let Some(preset) = self.preset {
+            preset.apply(options);
+        }
+        let difficulty = Difficulty::new_custom(
+            self.width.unwrap_or(options.map_size.0),
+            self.height.unwrap_or(options.map_size.1),
+            self.mines.unwrap_or(options.bomb_count),
+        )
+        .map_err(CliError::Board)?;
+        difficulty.apply(options);
+        if self.seed.is_some() {
+            options.seed = self.seed;
+        }
+        Ok(())
+    }
+
+    fn load_puzzle(&self) -> Result<Option<Puzzle>, CliError> {
+        let path = match &self.puzzle {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        let puzzle: Puzzle = read_ron(path)?;
+        puzzle.validate().map_err(CliError::Puzzle)?;
+        Ok(Some(puzzle))
+    }
+
+    fn load_replay(&self) -> Result<Option<Replay>, CliError> {
+        let path = match &self.replay {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+        let replay: Replay = read_ron(path)?;
+        replay.validate().map_err(CliError::Replay)?;
+        Ok(Some(replay))
+    }
+}
+
+fn read_ron<T: serde::de::DeserializeOwned>(path: &PathBuf) -> Result<T, CliError> {
+    let read_error = |error: String| CliError::Read {
+        path: path.clone(),
+        error,
+    };
+    let content = std::fs::read_to_string(path).map_err(|e| read_error(e.to_string()))?;
+    ron::de::from_str(&content).map_err(|e| read_error(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, CliError> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn flags_are_parsed_with_their_values() {
+        let cli = parse(&["--width", "20", "--mines", "30", "--seed", "42", "--theme", "Dark"])
+            .unwrap();
+        assert_eq!(cli.width, Some(20));
+        assert_eq!(cli.height, None);
+        assert_eq!(cli.mines, Some(30));
+        assert_eq!(cli.seed, Some(42));
+        assert_eq!(cli.theme.as_deref(), Some("Dark"));
+        assert!(!cli.help);
+    }
+
+    #[test]
+    fn preset_names_are_case_insensitive() {
+        let cli = parse(&["--preset", "EXPERT"]).unwrap();
+        assert_eq!(cli.preset, Some(Difficulty::Expert));
+        assert!(matches!(
+            parse(&["--preset", "impossible"]),
+            Err(CliError::UnknownPreset(name)) if name == "impossible"
+        ));
+    }
+
+    #[test]
+    fn bad_arguments_are_rejected() {
+        assert!(matches!(
+            parse(&["--width"]),
+            Err(CliError::MissingValue(flag)) if flag == "--width"
+        ));
+        assert!(matches!(
+            parse(&["--mines", "many"]),
+            Err(CliError::InvalidValue { flag, value }) if flag == "--mines" && value == "many"
+        ));
+        assert!(matches!(
+            parse(&["--fullscreen"]),
+            Err(CliError::UnknownArgument(arg)) if arg == "--fullscreen"
+        ));
+    }
+
+    #[test]
+    fn board_flags_override_the_preset_and_settings() {
+        let settings = BoardOptions {
+            map_size: (9, 9),
+            bomb_count: 10,
+            ..Default::default()
+        };
+        let launch = parse(&["--preset", "intermediate", "--mines", "50", "--seed", "7"])
+            .unwrap()
+            .resolve(&settings)
+            .unwrap();
+        let board = launch.board.unwrap();
+        assert_eq!(board.map_size, (16, 16));
+        assert_eq!(board.bomb_count, 50);
+        assert_eq!(board.seed, Some(7));
+    }
+
+    #[test]
+    fn menu_is_kept_without_board_flags() {
+        let launch = parse(&["--theme", "Dark"])
+            .unwrap()
+            .resolve(&BoardOptions::default())
+            .unwrap();
+        assert!(launch.board.is_none());
+        assert_eq!(launch.theme.as_deref(), Some("Dark"));
+    }
+
+    #[test]
+    fn invalid_boards_are_rejected() {
+        let result = parse(&["--width", "1"]).unwrap().resolve(&BoardOptions::default());
+        assert!(matches!(result, Err(CliError::Board(CustomGameError::Width))));
+    }
+
+    #[test]
+    fn replay_is_not_combined_with_a_board() {
+        for flags in [
+            ["--width", "20"],
+            ["--height", "20"],
+            ["--mines", "20"],
+            ["--preset", "expert"],
+            ["--seed", "20"],
+            ["--puzzle", "puzzle.ron"],
+        ] {
+            let mut args = vec!["--replay", "replay.ron"];
+            args.extend(flags);
+            let result = parse(&args).unwrap().resolve(&BoardOptions::default());
+            assert!(matches!(result, Err(CliError::ReplayWithBoard)), "{:?}", flags);
+        }
+    }
+}
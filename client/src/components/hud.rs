@@ -0,0 +1,22 @@
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct Hud;
+
+/// Text showing the bombs left to flag
+#[derive(Component)]
+pub struct MineCounterText;
+
+#[derive(Component)]
+pub struct HudTimeText;
+
+/// Text showing the number rule and countdown of the current game
+#[derive(Component)]
+pub struct HudModeText;
+
+/// Button starting a new board, its face shows the game state
+#[derive(Component)]
+pub struct ResetButton;
+
+#[derive(Component)]
+pub struct ResetFaceText;
@@ -0,0 +1,66 @@
+use crate::resources::{CustomField, Difficulty, KeyAction};
+use bevy::prelude::Component;
+
+#[derive(Component)]
+pub struct MenuScreen;
+
+#[derive(Component)]
+pub struct LevelSelectScreen;
+
+#[derive(Component)]
+pub struct CustomGameScreen;
+
+#[derive(Component)]
+pub struct SettingsScreen;
+
+#[derive(Component)]
+pub struct BestTimesScreen;
+
+/// Game over and victory screen
+#[derive(Component)]
+pub struct GameEndScreen;
+
+/// Text showing the value of a custom game field
+#[derive(Component)]
+pub struct CustomFieldText(pub CustomField);
+
+/// Text showing why the custom game can't be started
+#[derive(Component)]
+pub struct CustomGameErrorText;
+
+/// What clicking a menu button does
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum MenuButton {
+    Continue,
+    NewGame(Difficulty),
+    CustomGame,
+    FocusField(CustomField),
+    StartCustomGame,
+    Daily,
+    Puzzles,
+    Campaign,
+    Level(usize),
+    BestTimes,
+    CycleBestTimesDifficulty,
+    CycleBestTimesSort,
+    Settings,
+    ToggleSafeStart,
+    ToggleChording,
+    ToggleQuestionMarks,
+    CycleNumberRule,
+    CycleCountdown,
+    CycleTheme,
+    CycleWindowSize,
+    CyclePanButton,
+    ToggleReduceMotion,
+    CyclePalette,
+    CycleNumberCue,
+    CycleSound,
+    Rebind(KeyAction),
+    Back,
+    Retry,
+    NewBoard,
+    WatchReplay,
+    MainMenu,
+    Quit,
+}
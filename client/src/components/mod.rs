@@ -1,3 +1,7 @@
 mod pause;
+mod menu;
+mod hud;
 
-pub use pause::*;
\ No newline at end of file
+pub use pause::*;
+pub use menu::*;
+pub use hud::*;
\ No newline at end of file
@@ -0,0 +1,20 @@
+use crate::resources::Difficulty;
+
+/// Sent when the countdown of a time attack game reaches zero
+#[derive(Debug, Copy, Clone)]
+pub struct GameTimeoutEvent;
+
+/// Asks for a new board, sent by the menus
+#[derive(Debug, Copy, Clone)]
+pub enum NewGameEvent {
+    Difficulty(Difficulty),
+    /// Random board with the current options
+    Random,
+    /// Same board as the last game
+    Retry,
+    /// Plays back the moves of the last game
+    Replay,
+    NextPuzzle,
+    Daily,
+    Level(usize),
+}
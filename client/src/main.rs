@@ -2,96 +2,239 @@
 
 use bevy::log;
 use bevy::prelude::*;
-use board_plugin::events::*;
 use board_plugin::BoardPlugin;
 
+mod cli;
 mod resources;
 mod components;
+mod events;
+mod storage;
 mod systems;
 
-use systems::{clear_pause_screen, pause_screen, game_time_system, setup_board};
-use resources::{GameTime, AppState};
+use systems::{
+    clear_pause_screen, pause_screen, game_time_system, setup_board, theme_loading, load_puzzle_pack,
+    puzzle_handler, puzzle_completion_system, number_rule_indicator, countdown_setup_system,
+    opening_bonus_system, load_daily_results, daily_handler, daily_result_system,
+    hint_input_handler, game_stats_system, load_campaign, level_select_screen, level_handler,
+    campaign_completion_system, new_game_handler, menu_screen, custom_game_screen,
+    settings_screen, menu_action, custom_game_input, custom_game_text, hud_system,
+    hud_text_system, reset_button_action, replay_handler, replay_recorder, replay_playback,
+    game_end_system, game_end_screen, load_best_times, best_times_screen, settings_action,
+    rebind_system, screenshot_system, ui::{button_color_system, clear_screen},
+};
+use components::{
+    BestTimesScreen, CustomGameScreen, GameEndScreen, LevelSelectScreen, MenuScreen, SettingsScreen,
+};
+use cli::CliArgs;
+use events::{GameTimeoutEvent, NewGameEvent};
+use resources::{
+    GameTime, AppState, GameStats, Campaign, CampaignLoader, CustomGameForm,
+    BestTimesView, Rebinding, Settings, skip_state_error, ThemeIndex, ThemeIndexLoader,
+};
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::WorldInspectorPlugin;
 
+pub const TITLE: &str = "Mine Sweeper";
+
 fn main() {
     let mut app = App::new();
+    let settings = Settings::load();
+    let launch = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(args) if args.help => {
+            println!("{}", cli::USAGE);
+            return;
+        }
+        Ok(args) => args.resolve(&settings.board),
+        Err(e) => Err(e),
+    };
+    let launch = match launch {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, cli::USAGE);
+            std::process::exit(2);
+        }
+    };
 
     app.insert_resource(WindowDescriptor {
-        title: "Mine Sweeper".to_string(),
-        width: 800.,
-        height: 800.,
+        title: TITLE.to_string(),
+        width: settings.window_size.0,
+        height: settings.window_size.1,
         ..Default::default()
     })
+    .insert_resource(settings.camera.clone())
+    .insert_resource(settings.animation.clone())
+    .insert_resource(settings.numbers.clone())
+    .insert_resource(settings.countdown.clone())
+    .insert_resource(settings)
+    .insert_resource(launch)
+    .init_resource::<Rebinding>()
     .insert_resource(GameTime::new_paused())
-    .add_state(AppState::Out);
+    .init_resource::<GameStats>()
+    .init_resource::<CustomGameForm>()
+    .init_resource::<BestTimesView>()
+    .add_event::<GameTimeoutEvent>()
+    .add_event::<NewGameEvent>()
+    .add_asset::<Campaign>()
+    .init_asset_loader::<CampaignLoader>()
+    .add_asset::<ThemeIndex>()
+    .init_asset_loader::<ThemeIndexLoader>()
+    .add_state(AppState::Loading);
+    #[cfg(feature = "hot_reload")]
+    app.insert_resource(bevy::asset::AssetServerSettings {
+        watch_for_changes: true,
+        ..Default::default()
+    });
     app.add_plugins(DefaultPlugins);
     #[cfg(feature = "debug")]
     app.add_plugin(WorldInspectorPlugin::new());
     app.add_plugin(BoardPlugin {
         running_state: AppState::InGame,
     })
+    .add_system_set(SystemSet::on_update(AppState::Loading).with_system(theme_loading))
     .add_system_set(SystemSet::on_enter(AppState::Paused).with_system(pause_screen))
     .add_system_set(SystemSet::on_exit(AppState::Paused).with_system(clear_pause_screen))
-    .add_system_set(SystemSet::on_update(AppState::InGame).with_system(game_time_system))
-    .add_system(state_handler)
-    .add_system(game_state_handler)
+    .add_system_set(
+        SystemSet::on_update(AppState::InGame)
+            .with_system(game_time_system)
+            .with_system(replay_playback)
+            .with_system(opening_bonus_system)
+            .with_system(hint_input_handler)
+            .with_system(puzzle_completion_system)
+            .with_system(campaign_completion_system),
+    )
+    .add_system_set(SystemSet::on_enter(AppState::GameOver).with_system(game_end_screen))
+    .add_system_set(
+        SystemSet::on_exit(AppState::GameOver).with_system(clear_screen::<GameEndScreen>),
+    )
+    .add_system_set(SystemSet::on_enter(AppState::Won).with_system(game_end_screen))
+    .add_system_set(SystemSet::on_exit(AppState::Won).with_system(clear_screen::<GameEndScreen>))
+    .add_system_set(SystemSet::on_update(AppState::Menu).with_system(menu_screen))
+    .add_system_set(SystemSet::on_exit(AppState::Menu).with_system(clear_screen::<MenuScreen>))
+    .add_system_set(
+        SystemSet::on_update(AppState::CustomGame)
+            .with_system(custom_game_screen)
+            .with_system(custom_game_input)
+            .with_system(custom_game_text),
+    )
+    .add_system_set(
+        SystemSet::on_exit(AppState::CustomGame).with_system(clear_screen::<CustomGameScreen>),
+    )
+    .add_system_set(
+        SystemSet::on_update(AppState::Settings)
+            .with_system(settings_screen)
+            .with_system(settings_action)
+            .with_system(rebind_system),
+    )
+    .add_system_set(
+        SystemSet::on_exit(AppState::Settings).with_system(clear_screen::<SettingsScreen>),
+    )
+    .add_system_set(SystemSet::on_update(AppState::BestTimes).with_system(best_times_screen))
+    .add_system_set(
+        SystemSet::on_exit(AppState::BestTimes).with_system(clear_screen::<BestTimesScreen>),
+    )
+    .add_system_set(SystemSet::on_update(AppState::LevelSelect).with_system(level_select_screen))
+    .add_system_set(
+        SystemSet::on_exit(AppState::LevelSelect).with_system(clear_screen::<LevelSelectScreen>),
+    )
+    // Ending the game takes over a state key pressed on the same frame
+    .add_system(game_end_system.label("game_end"))
+    .add_system(state_handler.after("game_end"))
+    .add_system(menu_action)
+    .add_system(new_game_handler)
+    .add_system(replay_handler)
+    .add_system(replay_recorder)
+    .add_system(puzzle_handler)
+    .add_system(daily_handler)
+    .add_system(level_handler)
+    .add_system(number_rule_indicator)
+    .add_system(countdown_setup_system)
+    .add_system(daily_result_system)
+    .add_system(game_stats_system)
+    .add_system(hud_system)
+    .add_system(hud_text_system)
+    .add_system(reset_button_action)
+    .add_system(button_color_system)
+    .add_system(screenshot_system)
     .add_startup_system(camera_setup)
+    .add_startup_system(load_puzzle_pack)
+    .add_startup_system(load_daily_results)
+    .add_startup_system(load_campaign)
+    .add_startup_system(load_best_times)
     .add_startup_system(setup_board);
+    #[cfg(feature = "audio")]
+    app.init_resource::<resources::ThemeSounds>()
+        .add_system(systems::load_theme_sounds)
+        .add_system(systems::sound_system);
 
     app.run();
 }
 
 fn camera_setup(mut commands: Commands) {
     commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
 }
 
-fn game_state_handler(
+/// Applies the state keys, the gamepad Start button and gamepad disconnects. A single change is
+/// made per frame, one already queued by another system wins
+#[allow(clippy::too_many_arguments)]
+fn state_handler(
     mut state: ResMut<State<AppState>>,
-    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut gamepad_evr: EventReader<GamepadEvent>,
+    mut new_game_ewr: EventWriter<NewGameEvent>,
 ) {
-    if bomb_explosion_evr.iter().count() > 0 {
-        if state.current() == &AppState::InGame {
-            state.set(AppState::GameOver).unwrap();
+    // A player unplugging mid-game may be in the middle of a move
+    let disconnected = gamepad_evr
+        .iter()
+        .any(|GamepadEvent(_, event)| *event == GamepadEventType::Disconnected);
+    if rebinding.action.is_some() {
+        return;
+    }
+    let key_bindings = &settings.key_bindings;
+    let clear = keys.just_pressed(key_bindings.clear);
+    let menu = keys.just_pressed(key_bindings.menu);
+    let pause = keys.just_pressed(key_bindings.pause);
+    let start = gamepads
+        .iter()
+        .any(|g| gamepad_buttons.just_pressed(GamepadButton(*g, GamepadButtonType::Start)));
+    if keys.just_pressed(key_bindings.new_game) {
+        log::debug!("loading detected");
+        if matches!(
+            state.current(),
+            AppState::InGame | AppState::Out | AppState::GameOver | AppState::Won
+        ) {
+            new_game_ewr.send(NewGameEvent::Random);
         }
     }
-}
 
-fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>, mut game_time: ResMut<GameTime>) {
-    if keys.just_pressed(KeyCode::C) {
-        log::debug!("clearing detected");
-        if state.current() == &AppState::InGame {
+    let in_game_below = state.inactives().contains(&AppState::InGame);
+    let result = match state.current().clone() {
+        AppState::InGame if clear => {
             log::info!("clearing game");
-            state.set(AppState::Out).unwrap();
+            state.set(AppState::Out)
         }
-    }
-    if keys.just_pressed(KeyCode::G) {
-        log::debug!("loading detected");
-        game_time.reset();
-        game_time.pause();
-        match state.current() {
-            AppState::InGame => {
-                state.restart().unwrap();
+        AppState::InGame if menu => state.push(AppState::Menu),
+        AppState::InGame if pause || start || disconnected => {
+            if disconnected {
+                log::info!("Gamepad disconnected, pausing");
             }
-            AppState::Out => {
-                state.set(AppState::InGame).unwrap();
-            }
-            AppState::GameOver => {
-                state.set(AppState::InGame).unwrap();
-            }
-            _ => (),
-        };
-    }
-    if keys.just_pressed(KeyCode::Escape) {
-        match state.current() {
-            AppState::InGame => {
-                state.push(AppState::Paused).unwrap();
-            }
-            AppState::Paused => {
-                state.pop().unwrap();
-            }
-            _ => (),
-        };
-    }
+            state.push(AppState::Paused)
+        }
+        AppState::Paused | AppState::Out if menu => state.set(AppState::Menu),
+        AppState::GameOver | AppState::Won if menu => state.replace(AppState::Menu),
+        AppState::Paused if pause || start => state.pop(),
+        AppState::Menu if pause && in_game_below => state.pop(),
+        AppState::CustomGame | AppState::Settings | AppState::LevelSelect | AppState::BestTimes
+            if pause =>
+        {
+            state.set(AppState::Menu)
+        }
+        _ => Ok(()),
+    };
+    skip_state_error(result);
 }
@@ -1,7 +1,26 @@
+use bevy::ecs::schedule::StateError;
+use bevy::log;
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
+    /// Waiting for the theme before showing anything
+    Loading,
     InGame,
     Paused,
     GameOver,
+    Won,
+    LevelSelect,
+    Menu,
+    CustomGame,
+    Settings,
+    BestTimes,
     Out,
-}
\ No newline at end of file
+}
+
+/// Logs a state change that wasn't made, another change being already queued on this frame.
+/// The first change queued wins
+pub fn skip_state_error(result: Result<(), StateError>) {
+    if let Err(e) = result {
+        log::debug!("State change skipped: {:?}", e);
+    }
+}
@@ -0,0 +1,194 @@
+use crate::resources::{format_seconds, Difficulty};
+use crate::storage;
+use board_plugin::resources::NumberRule;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Winning games kept for each difficulty
+const TABLE_SIZE: usize = 10;
+
+/// How a game was played, best times are for classic boards of a random seed played unassisted
+#[derive(Debug, Copy, Clone, Default)]
+pub struct GameConditions {
+    pub number_rule: NumberRule,
+    pub countdown: bool,
+    /// The seed was given or the board retried, the player may know the layout
+    pub seed_chosen: bool,
+    /// A puzzle, daily challenge, campaign level or replay playback
+    pub special_mode: bool,
+    pub hints: u32,
+}
+
+impl GameConditions {
+    pub fn competes_for_best_times(&self) -> bool {
+        self.number_rule == NumberRule::Classic
+            && !self.countdown
+            && !self.seed_chosen
+            && !self.special_mode
+            && self.hints == 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BestTime {
+    /// Game time in seconds
+    pub time: f32,
+    pub date: NaiveDate,
+    pub bbbv_per_second: f32,
+    /// 3BV over clicks, as a percentage
+    pub efficiency: f32,
+    pub profile: String,
+    /// Whether the board was cleared without placing any flag
+    pub no_flags: bool,
+}
+
+impl BestTime {
+    pub fn row(&self, rank: usize) -> String {
+        format!(
+            "{:>2}. {} {:>5.2} 3BV/s {:>3.0}% {} {} {}",
+            rank,
+            format_seconds(self.time),
+            self.bbbv_per_second,
+            self.efficiency,
+            if self.no_flags { "NF" } else { "FL" },
+            self.date,
+            self.profile
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum BestTimeSort {
+    #[default]
+    Time,
+    BbbvPerSecond,
+    Efficiency,
+    Date,
+}
+
+impl BestTimeSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BestTimeSort::Time => "time",
+            BestTimeSort::BbbvPerSecond => "3BV/s",
+            BestTimeSort::Efficiency => "efficiency",
+            BestTimeSort::Date => "date",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            BestTimeSort::Time => BestTimeSort::BbbvPerSecond,
+            BestTimeSort::BbbvPerSecond => BestTimeSort::Efficiency,
+            BestTimeSort::Efficiency => BestTimeSort::Date,
+            BestTimeSort::Date => BestTimeSort::Time,
+        }
+    }
+
+    /// Best entries first
+    fn compare(&self, a: &BestTime, b: &BestTime) -> Ordering {
+        let ordering = match self {
+            BestTimeSort::Time => a.time.total_cmp(&b.time),
+            BestTimeSort::BbbvPerSecond => b.bbbv_per_second.total_cmp(&a.bbbv_per_second),
+            BestTimeSort::Efficiency => b.efficiency.total_cmp(&a.efficiency),
+            BestTimeSort::Date => b.date.cmp(&a.date),
+        };
+        ordering.then(a.time.total_cmp(&b.time))
+    }
+}
+
+/// Fastest wins of every preset and custom board, keyed by `Difficulty::label`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BestTimes {
+    tables: BTreeMap<String, Vec<BestTime>>,
+}
+
+impl BestTimes {
+    const STORAGE_KEY: &'static str = "best_times";
+
+    pub fn load() -> Self {
+        storage::load(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::STORAGE_KEY, self);
+    }
+
+    pub fn best(&self, difficulty: &Difficulty) -> Option<f32> {
+        self.tables
+            .get(&difficulty.label())
+            .and_then(|table| table.first())
+            .map(|entry| entry.time)
+    }
+
+    /// Adds a win to the table of its difficulty, returning whether it is the new best time
+    pub fn record(&mut self, difficulty: &Difficulty, entry: BestTime) -> bool {
+        let table = self.tables.entry(difficulty.label()).or_default();
+        let personal_best = table.first().is_none_or(|best| entry.time < best.time);
+        table.push(entry);
+        table.sort_by(|a, b| BestTimeSort::Time.compare(a, b));
+        table.truncate(TABLE_SIZE);
+        personal_best
+    }
+
+    /// Preset labels followed by the custom boards that have times
+    pub fn difficulties(&self) -> Vec<String> {
+        let mut labels: Vec<_> = Difficulty::PRESETS.iter().map(|d| d.label()).collect();
+        for label in self.tables.keys() {
+            if !labels.contains(label) {
+                labels.push(label.clone());
+            }
+        }
+        labels
+    }
+
+    pub fn sorted(&self, label: &str, sort: BestTimeSort) -> Vec<BestTime> {
+        let mut table = self.tables.get(label).cloned().unwrap_or_default();
+        table.sort_by(|a, b| sort.compare(a, b));
+        table
+    }
+}
+
+/// Difficulty and sorting shown on the best times screen
+#[derive(Debug, Default)]
+pub struct BestTimesView {
+    pub difficulty: usize,
+    pub sort: BestTimeSort,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_unassisted_classic_random_boards_compete() {
+        assert!(GameConditions::default().competes_for_best_times());
+        let others = [
+            GameConditions {
+                number_rule: NumberRule::Liar,
+                ..Default::default()
+            },
+            GameConditions {
+                countdown: true,
+                ..Default::default()
+            },
+            GameConditions {
+                seed_chosen: true,
+                ..Default::default()
+            },
+            GameConditions {
+                special_mode: true,
+                ..Default::default()
+            },
+            GameConditions {
+                hints: 1,
+                ..Default::default()
+            },
+        ];
+        for conditions in others {
+            assert!(!conditions.competes_for_best_times(), "{:?}", conditions);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use crate::storage;
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::log;
+use bevy::prelude::Handle;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use board_plugin::resources::BoardOptions;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Level {
+    pub name: String,
+    pub options: BoardOptions,
+    /// Time in seconds to beat for the third star
+    #[serde(default)]
+    pub target_time: Option<f32>,
+}
+
+impl Level {
+    /// One star for clearing the level, one for not using hints and one for beating the
+    /// target time, if any
+    pub fn stars(&self, time: f32, hints_used: bool) -> u8 {
+        let mut stars = 1;
+        if !hints_used {
+            stars += 1;
+        }
+        if self.target_time.is_none_or(|target| time <= target) {
+            stars += 1;
+        }
+        stars
+    }
+}
+
+/// Ordered list of levels, each one unlocked by clearing the previous one
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9b0f4c1e-6f0d-4a57-8d4e-3e2f8f7c5a21"]
+pub struct Campaign {
+    pub name: String,
+    pub levels: Vec<Level>,
+}
+
+#[derive(Default)]
+pub struct CampaignLoader;
+
+impl AssetLoader for CampaignLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let campaign: Campaign = ron::de::from_bytes(bytes)?;
+            log::info!("Loaded campaign {} ({} levels)", campaign.name, campaign.levels.len());
+            load_context.set_default_asset(LoadedAsset::new(campaign));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["campaign.ron"]
+    }
+}
+
+/// Level of the campaign being played
+pub struct ActiveLevel(pub usize);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelProgress {
+    /// Best time in seconds
+    pub best_time: f32,
+    pub stars: u8,
+}
+
+/// Campaign offered to the player and its stored progress, by level name
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct CampaignProgress {
+    #[serde(skip)]
+    pub campaign: Handle<Campaign>,
+    levels: BTreeMap<String, LevelProgress>,
+}
+
+impl CampaignProgress {
+    const STORAGE_KEY: &'static str = "campaign";
+
+    pub fn load(campaign: Handle<Campaign>) -> Self {
+        let progress: Self = storage::load(Self::STORAGE_KEY).unwrap_or_default();
+        Self {
+            campaign,
+            ..progress
+        }
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::STORAGE_KEY, self);
+    }
+
+    pub fn get(&self, level: &Level) -> Option<&LevelProgress> {
+        self.levels.get(&level.name)
+    }
+
+    pub fn is_unlocked(&self, campaign: &Campaign, index: usize) -> bool {
+        index == 0
+            || campaign
+                .levels
+                .get(index - 1)
+                .is_some_and(|previous| self.get(previous).is_some())
+    }
+
+    /// Keeps the best time and star rating of every clear
+    pub fn record(&mut self, level: &Level, time: f32, stars: u8) {
+        let progress = self
+            .levels
+            .entry(level.name.clone())
+            .or_insert(LevelProgress {
+                best_time: time,
+                stars,
+            });
+        progress.best_time = progress.best_time.min(time);
+        progress.stars = progress.stars.max(stars);
+    }
+}
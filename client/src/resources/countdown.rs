@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How the time budget of a countdown game is computed from the board
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum CountdownBudget {
+    /// Budget for every tile that isn't a bomb
+    PerSafeTile(Duration),
+    /// Budget for every click of the board's 3BV
+    PerBbbv(Duration),
+}
+
+/// Time attack settings, they apply when the next board is created
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CountdownOptions {
+    pub enabled: bool,
+    pub budget: CountdownBudget,
+    /// Time added every time an opening is cleared
+    pub opening_bonus: Duration,
+}
+
+impl Default for CountdownOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            budget: CountdownBudget::PER_BBBV,
+            opening_bonus: Duration::from_secs(3),
+        }
+    }
+}
+
+impl CountdownOptions {
+    /// Switches between no countdown, a 3BV budget and a board size budget
+    pub fn cycle(&mut self) {
+        match (self.enabled, self.budget) {
+            (false, _) => {
+                self.enabled = true;
+                self.budget = CountdownBudget::PER_BBBV;
+            }
+            (true, CountdownBudget::PerBbbv(_)) => self.budget = CountdownBudget::PER_SAFE_TILE,
+            (true, CountdownBudget::PerSafeTile(_)) => self.enabled = false,
+        }
+    }
+}
+
+impl CountdownBudget {
+    pub const PER_SAFE_TILE: Self = Self::PerSafeTile(Duration::from_secs(1));
+    pub const PER_BBBV: Self = Self::PerBbbv(Duration::from_secs(2));
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CountdownBudget::PerSafeTile(_) => "board size",
+            CountdownBudget::PerBbbv(_) => "3BV",
+        }
+    }
+
+    pub fn budget(&self, safe_tiles: u32, bbbv: u32) -> Duration {
+        match *self {
+            CountdownBudget::PerSafeTile(budget) => budget * safe_tiles,
+            CountdownBudget::PerBbbv(budget) => budget * bbbv,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+/// Field of the custom game dialog
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum CustomField {
+    Width,
+    Height,
+    BombCount,
+}
+
+impl CustomField {
+    pub const ALL: [CustomField; 3] = [CustomField::Width, CustomField::Height, CustomField::BombCount];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CustomField::Width => "Width",
+            CustomField::Height => "Height",
+            CustomField::BombCount => "Mines",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            CustomField::Width => CustomField::Height,
+            CustomField::Height => CustomField::BombCount,
+            CustomField::BombCount => CustomField::Width,
+        }
+    }
+}
+
+/// Values typed in the custom game dialog
+#[derive(Debug, Clone)]
+pub struct CustomGameForm {
+    pub width: String,
+    pub height: String,
+    pub bomb_count: String,
+    pub focused: CustomField,
+    pub error: Option<String>,
+}
+
+impl Default for CustomGameForm {
+    fn default() -> Self {
+        Self {
+            width: "20".to_string(),
+            height: "20".to_string(),
+            bomb_count: "60".to_string(),
+            focused: CustomField::Width,
+            error: None,
+        }
+    }
+}
+
+impl CustomGameForm {
+    const MAX_LENGTH: usize = 5;
+
+    pub fn value(&self, field: CustomField) -> &str {
+        match field {
+            CustomField::Width => &self.width,
+            CustomField::Height => &self.height,
+            CustomField::BombCount => &self.bomb_count,
+        }
+    }
+
+    fn value_mut(&mut self, field: CustomField) -> &mut String {
+        match field {
+            CustomField::Width => &mut self.width,
+            CustomField::Height => &mut self.height,
+            CustomField::BombCount => &mut self.bomb_count,
+        }
+    }
+
+    pub fn push(&mut self, c: char) {
+        let value = self.value_mut(self.focused);
+        if c.is_ascii_digit() && value.len() < Self::MAX_LENGTH {
+            value.push(c);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.value_mut(self.focused).pop();
+    }
+}
@@ -0,0 +1,155 @@
+use crate::resources::format_seconds;
+use crate::storage;
+use board_plugin::resources::BoardOptions;
+use chrono::{Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Daily challenge being played, everyone gets the same board on the same UTC date
+pub struct DailyChallenge {
+    pub date: NaiveDate,
+    /// Whether the result of this attempt was already recorded
+    pub recorded: bool,
+}
+
+impl DailyChallenge {
+    pub fn today() -> Self {
+        Self {
+            date: Utc::now().date_naive(),
+            recorded: false,
+        }
+    }
+
+    /// FNV-1a hash of the date, stable across platforms and releases like the board generated
+    /// from it, see [`BoardOptions::seed`]
+    pub fn seed(&self) -> u64 {
+        self.date
+            .to_string()
+            .bytes()
+            .fold(0xcbf29ce484222325, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            })
+    }
+
+    pub fn board_options(&self) -> BoardOptions {
+        BoardOptions {
+            map_size: (16, 16),
+            bomb_count: 40,
+            tile_padding: 1.0,
+            safe_start: true,
+            seed: Some(self.seed()),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyResult {
+    pub won: bool,
+    /// Game time in seconds
+    pub time: f32,
+    pub bbbv: u32,
+    pub hints_used: bool,
+}
+
+impl DailyResult {
+    pub fn bbbv_per_second(&self) -> f32 {
+        if self.time > 0. {
+            self.bbbv as f32 / self.time
+        } else {
+            0.
+        }
+    }
+
+    pub fn share_string(&self, date: NaiveDate, streak: u32) -> String {
+        let outcome = if self.won {
+            format!("cleared in {}", format_seconds(self.time))
+        } else {
+            format!("lost after {}", format_seconds(self.time))
+        };
+        let hints = if self.hints_used { "with hints" } else { "no hints" };
+        format!(
+            "Mine Sweeper Daily {} - {} - 3BV/s {:.2} - {} - streak {}",
+            date,
+            outcome,
+            self.bbbv_per_second(),
+            hints,
+            streak
+        )
+    }
+}
+
+/// Stored result of every daily challenge played, by date
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct DailyResults {
+    results: BTreeMap<NaiveDate, DailyResult>,
+}
+
+impl DailyResults {
+    const STORAGE_KEY: &'static str = "daily";
+
+    pub fn load() -> Self {
+        storage::load(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::STORAGE_KEY, self);
+    }
+
+    pub fn get(&self, date: NaiveDate) -> Option<&DailyResult> {
+        self.results.get(&date)
+    }
+
+    /// Keeps the first result of the day, unless a later attempt is the first win
+    pub fn record(&mut self, date: NaiveDate, result: DailyResult) {
+        let replace = match self.results.get(&date) {
+            None => true,
+            Some(previous) => !previous.won && result.won,
+        };
+        if replace {
+            self.results.insert(date, result);
+        }
+    }
+
+    /// Consecutive days won up to `today`, or up to yesterday while today isn't won yet
+    pub fn streak(&self, today: NaiveDate) -> u32 {
+        let won = |date: &NaiveDate| self.results.get(date).is_some_and(|r| r.won);
+        let mut date = if won(&today) {
+            today
+        } else {
+            today - Duration::days(1)
+        };
+        let mut streak = 0;
+        while won(&date) {
+            streak += 1;
+            date -= Duration::days(1);
+        }
+        streak
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(year: i32, month: u32, day: u32) -> DailyChallenge {
+        DailyChallenge {
+            date: NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            recorded: false,
+        }
+    }
+
+    #[test]
+    fn seed_is_the_fnv1a_hash_of_the_date() {
+        // Changing it changes every past and future daily board
+        assert_eq!(challenge(2024, 1, 1).seed(), 0x3763_7d7a_01d6_21a9);
+    }
+
+    #[test]
+    fn every_date_gets_its_own_seed() {
+        let seed = challenge(2024, 1, 1).seed();
+        assert_ne!(challenge(2024, 1, 2).seed(), seed);
+        assert_ne!(challenge(2025, 1, 1).seed(), seed);
+        assert_eq!(challenge(2024, 1, 1).board_options().seed, Some(seed));
+    }
+}
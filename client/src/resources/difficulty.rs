@@ -0,0 +1,183 @@
+use board_plugin::resources::BoardOptions;
+use std::fmt::{self, Display, Formatter};
+
+pub const MAX_MAP_SIDE: u16 = 100;
+
+/// Board size and bomb count picked from the menu
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Expert,
+    Custom {
+        width: u16,
+        height: u16,
+        bomb_count: u16,
+    },
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CustomGameError {
+    Width,
+    Height,
+    BombCount { max: u32 },
+}
+
+impl Display for CustomGameError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            CustomGameError::Width => {
+                write!(f, "Width must be between 2 and {}", MAX_MAP_SIDE)
+            }
+            CustomGameError::Height => {
+                write!(f, "Height must be between 2 and {}", MAX_MAP_SIDE)
+            }
+            CustomGameError::BombCount { max: 0 } => {
+                write!(f, "Board too small for mines around a safe start")
+            }
+            CustomGameError::BombCount { max } => {
+                write!(f, "Mines must be between 1 and {}", max)
+            }
+        }
+    }
+}
+
+impl Difficulty {
+    pub const PRESETS: [Difficulty; 3] = [
+        Difficulty::Beginner,
+        Difficulty::Intermediate,
+        Difficulty::Expert,
+    ];
+
+    /// Parses and checks the fields of the custom game dialog
+    pub fn custom(width: &str, height: &str, bomb_count: &str) -> Result<Self, CustomGameError> {
+        Self::new_custom(
+            width.parse().map_err(|_| CustomGameError::Width)?,
+            height.parse().map_err(|_| CustomGameError::Height)?,
+            bomb_count.parse().unwrap_or(0),
+        )
+    }
+
+    /// Checks a custom board. At least one tile is kept free of bombs and of bomb neighbors, so
+    /// the board can have a safe start: a 3x3 area, less along the sides of narrow boards.
+    pub fn new_custom(width: u16, height: u16, bomb_count: u16) -> Result<Self, CustomGameError> {
+        let side = |value: u16| Some(value).filter(|v| (2..=MAX_MAP_SIDE).contains(v));
+        let width = side(width).ok_or(CustomGameError::Width)?;
+        let height = side(height).ok_or(CustomGameError::Height)?;
+        let safe_area = width.min(3) as u32 * height.min(3) as u32;
+        let max = width as u32 * height as u32 - safe_area;
+        if bomb_count < 1 || bomb_count as u32 > max {
+            return Err(CustomGameError::BombCount { max });
+        }
+        Ok(Self::Custom {
+            width,
+            height,
+            bomb_count,
+        })
+    }
+
+    /// Preset from its name, case insensitive
+    pub fn preset(name: &str) -> Option<Self> {
+        Self::PRESETS
+            .into_iter()
+            .find(|d| d.label().eq_ignore_ascii_case(name))
+    }
+
+    /// Preset matching the board, or a custom difficulty
+    pub fn from_board(map_size: (u16, u16), bomb_count: u16) -> Self {
+        Self::PRESETS
+            .into_iter()
+            .find(|d| d.map_size() == map_size && d.bomb_count() == bomb_count)
+            .unwrap_or(Self::Custom {
+                width: map_size.0,
+                height: map_size.1,
+                bomb_count,
+            })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            Difficulty::Beginner => "Beginner".to_string(),
+            Difficulty::Intermediate => "Intermediate".to_string(),
+            Difficulty::Expert => "Expert".to_string(),
+            Difficulty::Custom {
+                width,
+                height,
+                bomb_count,
+            } => format!("Custom {}x{} {}", width, height, bomb_count),
+        }
+    }
+
+    pub fn map_size(&self) -> (u16, u16) {
+        match *self {
+            Difficulty::Beginner => (9, 9),
+            Difficulty::Intermediate => (16, 16),
+            Difficulty::Expert => (30, 16),
+            Difficulty::Custom { width, height, .. } => (width, height),
+        }
+    }
+
+    pub fn bomb_count(&self) -> u16 {
+        match *self {
+            Difficulty::Beginner => 10,
+            Difficulty::Intermediate => 40,
+            Difficulty::Expert => 99,
+            Difficulty::Custom { bomb_count, .. } => bomb_count,
+        }
+    }
+
+    /// Sets the board size and bomb count, keeping the other options
+    pub fn apply(&self, options: &mut BoardOptions) {
+        options.map_size = self.map_size();
+        options.bomb_count = self.bomb_count();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sides_must_be_between_2_and_the_max() {
+        assert_eq!(Difficulty::new_custom(1, 10, 5), Err(CustomGameError::Width));
+        assert_eq!(Difficulty::new_custom(10, MAX_MAP_SIDE + 1, 5), Err(CustomGameError::Height));
+        assert!(Difficulty::new_custom(2, MAX_MAP_SIDE, 5).is_ok());
+    }
+
+    #[test]
+    fn bombs_leave_room_for_a_safe_start() {
+        // 3x3 safe area on a 10x10 board
+        assert_eq!(
+            Difficulty::new_custom(10, 10, 92),
+            Err(CustomGameError::BombCount { max: 91 })
+        );
+        assert!(Difficulty::new_custom(10, 10, 91).is_ok());
+        assert_eq!(
+            Difficulty::new_custom(10, 10, 0),
+            Err(CustomGameError::BombCount { max: 91 })
+        );
+        // Only a 2x2 area fits on a 2x2 board
+        assert_eq!(Difficulty::new_custom(2, 2, 1), Err(CustomGameError::BombCount { max: 0 }));
+        // Narrow boards keep a 2x3 area
+        assert!(Difficulty::new_custom(2, 10, 14).is_ok());
+        assert!(Difficulty::new_custom(2, 10, 15).is_err());
+    }
+
+    #[test]
+    fn dialog_fields_must_be_numbers() {
+        assert_eq!(Difficulty::custom("wide", "10", "5"), Err(CustomGameError::Width));
+        assert_eq!(Difficulty::custom("10", "", "5"), Err(CustomGameError::Height));
+        assert_eq!(
+            Difficulty::custom("10", "10", "some"),
+            Err(CustomGameError::BombCount { max: 91 })
+        );
+        assert_eq!(
+            Difficulty::custom("16", "16", "40"),
+            Ok(Difficulty::Custom {
+                width: 16,
+                height: 16,
+                bomb_count: 40
+            })
+        );
+    }
+}
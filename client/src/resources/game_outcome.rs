@@ -0,0 +1,39 @@
+/// How the last game ended
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum GameOutcome {
+    Won,
+    Exploded,
+    TimedOut,
+}
+
+/// Results of the last game, shown on the game over and victory screens
+#[derive(Debug, Clone)]
+pub struct GameSummary {
+    pub outcome: GameOutcome,
+    /// Game time in seconds
+    pub time: f32,
+    pub bbbv: u32,
+    pub clicks: u32,
+    /// Best time of the board size before this game
+    pub previous_best: Option<f32>,
+    pub personal_best: bool,
+}
+
+impl GameSummary {
+    pub fn bbbv_per_second(&self) -> f32 {
+        if self.time > 0. {
+            self.bbbv as f32 / self.time
+        } else {
+            0.
+        }
+    }
+
+    /// 3BV over clicks, as a percentage
+    pub fn efficiency(&self) -> f32 {
+        if self.clicks > 0 {
+            self.bbbv as f32 * 100. / self.clicks as f32
+        } else {
+            0.
+        }
+    }
+}
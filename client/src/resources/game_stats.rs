@@ -0,0 +1,8 @@
+/// Player actions during the current game
+#[derive(Debug, Default)]
+pub struct GameStats {
+    pub hints: u32,
+    /// Uncover, mark and chord clicks on the board
+    pub clicks: u32,
+    pub flags: u32,
+}
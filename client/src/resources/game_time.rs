@@ -1,34 +1,75 @@
-use bevy::{prelude::*, core::Stopwatch};
+use bevy::core::Stopwatch;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+use std::time::Duration;
 
-#[derive(Deref, DerefMut)]
-pub struct GameTime(Stopwatch);
+pub struct GameTime {
+    stopwatch: Stopwatch,
+    /// Time budget of a countdown game, bonuses included
+    countdown: Option<Duration>,
+}
 
-impl GameTime {
-    pub fn to_string(&self) -> String {
-        let duration = self.elapsed();
-        let minutes = (duration.as_secs() as f32 / 60.).floor() as u16;
-        let seconds = (duration.as_secs() % 60) as u16;
-        let minute_string = if minutes < 10 {
-            format!("0{}", minutes)
-        } else {
-            format!("{}", minutes)
-        };
-        let second_string = if seconds < 10 {
-            format!("0{}", seconds)
-        } else {
-            format!("{}", seconds)
-        };
-
-        format!("{}:{}", minute_string, second_string)
+impl Deref for GameTime {
+    type Target = Stopwatch;
+
+    fn deref(&self) -> &Self::Target {
+        &self.stopwatch
     }
+}
 
+impl DerefMut for GameTime {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.stopwatch
+    }
+}
+
+impl Display for GameTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let seconds = self.remaining().unwrap_or_else(|| self.elapsed()).as_secs_f32();
+        let minutes = (seconds / 60.).floor() as u32;
+        write!(f, "{:02}:{:04.1}", minutes, seconds - minutes as f32 * 60.)
+    }
+}
+
+impl GameTime {
     pub fn new_paused() -> Self {
         let mut game_time_watch = Stopwatch::new();
         game_time_watch.pause();
-        Self(game_time_watch)
+        Self {
+            stopwatch: game_time_watch,
+            countdown: None,
+        }
     }
 
     pub fn reset(&mut self) {
-        self.0.reset();
-    } 
-}
\ No newline at end of file
+        self.stopwatch.reset();
+        self.countdown = None;
+    }
+
+    /// Turns the game time into a countdown from `budget`
+    pub fn start_countdown(&mut self, budget: Duration) {
+        self.countdown = Some(budget);
+    }
+
+    pub fn add_bonus(&mut self, bonus: Duration) {
+        if let Some(countdown) = self.countdown.as_mut() {
+            *countdown += bonus;
+        }
+    }
+
+    /// Time left in a countdown game
+    pub fn remaining(&self) -> Option<Duration> {
+        self.countdown
+            .map(|countdown| countdown.saturating_sub(self.elapsed()))
+    }
+
+    pub fn is_timed_out(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+}
+
+/// Formats a game time with hundredths of a second, like `01:23.45`
+pub fn format_seconds(seconds: f32) -> String {
+    let minutes = (seconds / 60.).floor() as u32;
+    format!("{:02}:{:05.2}", minutes, seconds - minutes as f32 * 60.)
+}
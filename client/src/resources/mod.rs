@@ -1,5 +1,33 @@
 mod game_time;
 mod app_state;
+mod puzzle_progress;
+mod countdown;
+mod game_outcome;
+mod daily;
+mod game_stats;
+mod campaign;
+mod difficulty;
+mod custom_game;
+mod best_times;
+mod profile;
+mod replay;
+mod settings;
+mod sound;
+mod theme_index;
 
 pub use game_time::*;
-pub use app_state::*;
\ No newline at end of file
+pub use app_state::*;
+pub use puzzle_progress::*;
+pub use countdown::*;
+pub use game_outcome::*;
+pub use daily::*;
+pub use game_stats::*;
+pub use campaign::*;
+pub use difficulty::*;
+pub use custom_game::*;
+pub use best_times::*;
+pub use profile::*;
+pub use replay::*;
+pub use settings::*;
+pub use sound::*;
+pub use theme_index::*;
\ No newline at end of file
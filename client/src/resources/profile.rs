@@ -0,0 +1,29 @@
+use crate::storage;
+use serde::{Deserialize, Serialize};
+
+/// Player name written with the best times
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        let name = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_else(|_| "Player".to_string());
+        Self { name }
+    }
+}
+
+impl Profile {
+    const STORAGE_KEY: &'static str = "profile";
+
+    pub fn load() -> Self {
+        storage::load(Self::STORAGE_KEY).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save(Self::STORAGE_KEY, self);
+    }
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use board_plugin::resources::PuzzlePack;
+
+/// Puzzle pack offered to the player and which of its puzzles were completed
+pub struct PuzzleProgress {
+    pub pack: Handle<PuzzlePack>,
+    pub current: Option<usize>,
+    completed: HashSet<String>,
+}
+
+impl PuzzleProgress {
+    pub fn new(pack: Handle<PuzzlePack>) -> Self {
+        Self {
+            pack,
+            current: None,
+            completed: HashSet::default(),
+        }
+    }
+
+    pub fn is_completed(&self, puzzle_name: &str) -> bool {
+        self.completed.contains(puzzle_name)
+    }
+
+    pub fn complete(&mut self, puzzle_name: &str) {
+        self.completed.insert(puzzle_name.to_string());
+    }
+
+    /// First puzzle after the current one that wasn't completed yet, or simply the next one
+    /// once the whole pack is done
+    pub fn next_puzzle(&self, pack: &PuzzlePack) -> Option<usize> {
+        let count = pack.puzzles.len();
+        if count == 0 {
+            return None;
+        }
+        let start = self.current.map_or(0, |i| i + 1);
+        (start..start + count)
+            .map(|i| i % count)
+            .find(|i| !self.is_completed(&pack.puzzles[*i].name))
+            .or(Some(start % count))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::resources::{CustomGameError, Difficulty};
+use board_plugin::components::Coordinates;
+use board_plugin::resources::{BoardOptions, Puzzle, PuzzleError};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum ReplayAction {
+    Uncover(Coordinates),
+    Mark(Coordinates),
+    UncoverAdjacent(Coordinates),
+}
+
+impl ReplayAction {
+    /// Tile the action is played on
+    pub fn coordinates(&self) -> Coordinates {
+        match self {
+            ReplayAction::Uncover(c)
+            | ReplayAction::Mark(c)
+            | ReplayAction::UncoverAdjacent(c) => *c,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct ReplayMove {
+    /// Game time of the move in seconds
+    pub time: f32,
+    pub action: ReplayAction,
+}
+
+/// Everything needed to play a game again: the board and the player moves
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Replay {
+    /// Seed of the bomb layout, see [`BoardOptions::seed`]
+    pub seed: u64,
+    pub options: BoardOptions,
+    pub puzzle: Option<Puzzle>,
+    pub moves: Vec<ReplayMove>,
+}
+
+impl Replay {
+    /// Size of the replayed board, the puzzle's when there is one
+    pub fn map_size(&self) -> (u16, u16) {
+        match &self.puzzle {
+            Some(puzzle) => puzzle.map_size,
+            None => self.options.map_size,
+        }
+    }
+
+    /// Checks the replayed puzzle, or the board size and bomb count the way the custom game
+    /// dialog does, and that every move is on the board
+    pub fn validate(&self) -> Result<(), ReplayError> {
+        match &self.puzzle {
+            Some(puzzle) => puzzle.validate().map_err(ReplayError::Puzzle)?,
+            None => {
+                let (width, height) = self.options.map_size;
+                Difficulty::new_custom(width, height, self.options.bomb_count)
+                    .map_err(ReplayError::Board)?;
+            }
+        }
+        let (width, height) = self.map_size();
+        let off_board = self
+            .moves
+            .iter()
+            .map(|m| m.action.coordinates())
+            .find(|c| c.x >= width || c.y >= height);
+        match off_board {
+            Some(c) => Err(ReplayError::OutOfBounds(c)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ReplayError {
+    Puzzle(PuzzleError),
+    Board(CustomGameError),
+    OutOfBounds(Coordinates),
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Puzzle(e) => write!(f, "invalid puzzle: {}", e),
+            ReplayError::Board(e) => write!(f, "invalid board: {}", e),
+            ReplayError::OutOfBounds(c) => write!(f, "move on tile {} is out of the map", c),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+/// Replay being played back on the current board
+#[derive(Debug, Clone)]
+pub struct ReplayPlayback {
+    pub replay: Replay,
+    pub next: usize,
+    /// Playback time in seconds, starting below zero to show the board before the first move
+    pub elapsed: f32,
+}
+
+impl ReplayPlayback {
+    const LEAD_IN: f32 = 0.5;
+
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next: 0,
+            elapsed: -Self::LEAD_IN,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.replay.moves.len()
+    }
+}
@@ -0,0 +1,235 @@
+use crate::resources::{CountdownOptions, SoundSettings};
+use crate::storage::{self, Location};
+use bevy::prelude::{KeyCode, MouseButton};
+use board_plugin::resources::{
+    AnimationOptions, BoardOptions, CameraControls, NumberStyle, Palette, CURSOR_KEYS,
+};
+use serde::{Deserialize, Serialize};
+
+/// Window sizes offered by the settings screen
+pub const WINDOW_SIZES: [(f32, f32); 4] = [(800., 800.), (1024., 768.), (1280., 960.), (1600., 1000.)];
+
+/// Mouse buttons the settings screen offers for panning the board, besides space + drag
+pub const PAN_BUTTONS: [Option<MouseButton>; 3] = [None, Some(MouseButton::Middle), Some(MouseButton::Right)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum KeyAction {
+    Pause,
+    Menu,
+    NewGame,
+    Clear,
+    Hint,
+    Screenshot,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 6] = [
+        KeyAction::Pause,
+        KeyAction::Menu,
+        KeyAction::NewGame,
+        KeyAction::Clear,
+        KeyAction::Hint,
+        KeyAction::Screenshot,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyAction::Pause => "Pause / back",
+            KeyAction::Menu => "Menu",
+            KeyAction::NewGame => "New game",
+            KeyAction::Clear => "Clear board",
+            KeyAction::Hint => "Hint",
+            KeyAction::Screenshot => "Board image",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBindings {
+    pub pause: KeyCode,
+    pub menu: KeyCode,
+    pub new_game: KeyCode,
+    pub clear: KeyCode,
+    pub hint: KeyCode,
+    /// Saves an image of the board, of its solution with shift held
+    pub screenshot: KeyCode,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            pause: KeyCode::Escape,
+            menu: KeyCode::M,
+            new_game: KeyCode::G,
+            clear: KeyCode::C,
+            hint: KeyCode::T,
+            screenshot: KeyCode::F12,
+        }
+    }
+}
+
+impl KeyBindings {
+    pub fn get(&self, action: KeyAction) -> KeyCode {
+        match action {
+            KeyAction::Pause => self.pause,
+            KeyAction::Menu => self.menu,
+            KeyAction::NewGame => self.new_game,
+            KeyAction::Clear => self.clear,
+            KeyAction::Hint => self.hint,
+            KeyAction::Screenshot => self.screenshot,
+        }
+    }
+
+    pub fn set(&mut self, action: KeyAction, key: KeyCode) {
+        let binding = match action {
+            KeyAction::Pause => &mut self.pause,
+            KeyAction::Menu => &mut self.menu,
+            KeyAction::NewGame => &mut self.new_game,
+            KeyAction::Clear => &mut self.clear,
+            KeyAction::Hint => &mut self.hint,
+            KeyAction::Screenshot => &mut self.screenshot,
+        };
+        *binding = key;
+    }
+}
+
+/// Player preferences, read from the config directory at startup and written back on change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub window_size: (f32, f32),
+    /// Options of random boards, keeping the last difficulty played
+    pub board: BoardOptions,
+    pub countdown: CountdownOptions,
+    pub theme: String,
+    pub key_bindings: KeyBindings,
+    pub camera: CameraControls,
+    pub animation: AnimationOptions,
+    pub numbers: NumberStyle,
+    pub sound: SoundSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            window_size: WINDOW_SIZES[0],
+            board: BoardOptions {
+                map_size: (9, 9),
+                bomb_count: 10,
+                tile_padding: 1.0,
+                safe_start: true,
+                ..Default::default()
+            },
+            countdown: Default::default(),
+            theme: "Default".to_string(),
+            key_bindings: Default::default(),
+            camera: Default::default(),
+            animation: Default::default(),
+            numbers: Default::default(),
+            sound: Default::default(),
+        }
+    }
+}
+
+impl Settings {
+    const STORAGE_KEY: &'static str = "settings";
+
+    pub fn load() -> Self {
+        storage::read(Location::Config, Self::STORAGE_KEY)
+            .map_or_else(Self::default, |content| Self::parse(&content))
+    }
+
+    /// Settings of a stored document, the defaults if it is corrupt
+    fn parse(content: &str) -> Self {
+        storage::parse(Self::STORAGE_KEY, content).unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        storage::save_to(Location::Config, Self::STORAGE_KEY, self);
+    }
+
+    pub fn next_window_size(&self) -> (f32, f32) {
+        let index = WINDOW_SIZES.iter().position(|s| *s == self.window_size);
+        WINDOW_SIZES[index.map_or(0, |i| (i + 1) % WINDOW_SIZES.len())]
+    }
+
+    /// Next palette of the settings screen, the theme colors coming before the first one
+    pub fn next_palette(&self) -> Option<Palette> {
+        match self.numbers.palette {
+            None => Some(Palette::ALL[0]),
+            Some(palette) if palette.next() == Palette::ALL[0] => None,
+            Some(palette) => Some(palette.next()),
+        }
+    }
+
+    pub fn next_pan_button(&self) -> Option<MouseButton> {
+        let index = PAN_BUTTONS.iter().position(|b| *b == self.camera.pan_button);
+        PAN_BUTTONS[index.map_or(0, |i| (i + 1) % PAN_BUTTONS.len())]
+    }
+
+    /// What already reads `key` besides `action`: the board controls, whose keys are fixed, or
+    /// another action
+    pub fn key_conflict(&self, action: KeyAction, key: KeyCode) -> Option<&'static str> {
+        if CURSOR_KEYS.contains(&key) {
+            return Some("the tile cursor");
+        }
+        if self.camera.uses_key(key) {
+            return Some("the board camera");
+        }
+        KeyAction::ALL
+            .into_iter()
+            .filter(|other| *other != action)
+            .find(|other| self.key_bindings.get(*other) == key)
+            .map(|other| other.label())
+    }
+}
+
+/// Action waiting for a key press on the settings screen
+#[derive(Debug, Default)]
+pub struct Rebinding {
+    pub action: Option<KeyAction>,
+    /// Last key refused for the action and what already uses it
+    pub refused: Option<(KeyCode, &'static str)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_used_elsewhere_conflict() {
+        let settings = Settings::default();
+        assert_eq!(settings.key_conflict(KeyAction::Hint, CURSOR_KEYS[0]), Some("the tile cursor"));
+        assert_eq!(
+            settings.key_conflict(KeyAction::Hint, settings.key_bindings.clear),
+            Some("Clear board")
+        );
+        // Keeping the key of the action itself is not a conflict
+        assert_eq!(settings.key_conflict(KeyAction::Hint, settings.key_bindings.hint), None);
+        assert_eq!(settings.key_conflict(KeyAction::Hint, KeyCode::F5), None);
+    }
+
+    #[test]
+    fn camera_keys_conflict() {
+        let mut settings = Settings::default();
+        settings.camera.pan_key = KeyCode::P;
+        assert_eq!(settings.key_conflict(KeyAction::Hint, KeyCode::P), Some("the board camera"));
+        assert_eq!(settings.key_conflict(KeyAction::Hint, KeyCode::Space), None);
+    }
+
+    #[test]
+    fn corrupt_settings_fall_back_to_the_defaults() {
+        let settings = Settings::parse("(window_size: (1024., 768.), theme: ");
+        assert_eq!(settings.window_size, Settings::default().window_size);
+        assert_eq!(settings.theme, "Default");
+    }
+
+    #[test]
+    fn missing_settings_keep_their_defaults() {
+        let settings = Settings::parse("(window_size: (1024., 768.))");
+        assert_eq!(settings.window_size, (1024., 768.));
+        assert_eq!(settings.board.bomb_count, Settings::default().board.bomb_count);
+        assert_eq!(settings.key_bindings.hint, KeyCode::T);
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(feature = "audio")]
+use bevy::prelude::*;
+#[cfg(feature = "audio")]
+use bevy::utils::HashMap;
+#[cfg(feature = "audio")]
+use board_plugin::resources::SoundEffect;
+use serde::{Deserialize, Serialize};
+
+/// Volumes offered by the settings screen
+pub const VOLUMES: [f32; 4] = [0.25, 0.5, 0.75, 1.];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundSettings {
+    /// Between 0 and 1
+    pub volume: f32,
+    pub muted: bool,
+    /// Shortest time in seconds before an effect plays again, so big cascades and chords don't
+    /// stack copies of the same sound
+    pub min_interval: f32,
+}
+
+impl Default for SoundSettings {
+    fn default() -> Self {
+        Self {
+            volume: 0.75,
+            muted: false,
+            min_interval: 0.08,
+        }
+    }
+}
+
+impl SoundSettings {
+    /// Next step of the settings screen: every volume from the lowest, then muted
+    pub fn cycle(&mut self) {
+        if self.muted {
+            self.muted = false;
+            self.volume = VOLUMES[0];
+            return;
+        }
+        match VOLUMES.iter().position(|v| *v > self.volume) {
+            Some(index) => self.volume = VOLUMES[index],
+            None => self.muted = true,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        if self.muted {
+            "off".to_string()
+        } else {
+            format!("{}%", (self.volume * 100.).round())
+        }
+    }
+}
+
+/// Sounds of the active theme, loaded from `BoardAssets::sounds`
+#[cfg(feature = "audio")]
+#[derive(Default)]
+pub struct ThemeSounds(pub HashMap<SoundEffect, Handle<AudioSource>>);
@@ -0,0 +1,47 @@
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::log;
+use bevy::prelude::Handle;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+/// Themes offered by the settings screen, a theme `Name` being read from `themes/name.theme.ron`
+#[derive(Debug, Clone, Deserialize, TypeUuid)]
+#[uuid = "4d7a2c93-1b5e-4f08-a6c1-7e93b0d25f64"]
+pub struct ThemeIndex {
+    pub themes: Vec<String>,
+}
+
+impl ThemeIndex {
+    /// Theme after `theme` in the list, wrapping around, the first one when `theme` isn't listed
+    pub fn next(&self, theme: &str) -> Option<&str> {
+        let index = self.themes.iter().position(|t| t == theme);
+        let next = index.map_or(0, |i| (i + 1) % self.themes.len());
+        self.themes.get(next).map(String::as_str)
+    }
+}
+
+#[derive(Default)]
+pub struct ThemeIndexLoader;
+
+impl AssetLoader for ThemeIndexLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let index: ThemeIndex = ron::de::from_bytes(bytes)?;
+            log::info!("Loaded theme index ({} themes)", index.themes.len());
+            load_context.set_default_asset(LoadedAsset::new(index));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["themes.ron"]
+    }
+}
+
+/// Theme index of the game, loaded at startup
+pub struct Themes(pub Handle<ThemeIndex>);
@@ -0,0 +1,125 @@
+//! Local persistence of small RON documents: files in the platform data or config directory on
+//! native, browser local storage on wasm.
+
+use bevy::log;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+const APP_DIRECTORY: &str = "minesweeper";
+
+/// Where a document is stored on native platforms
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Location {
+    Data,
+    Config,
+}
+
+/// Loads the document stored under `key`, `None` if it is missing or can't be read
+pub fn load<T: DeserializeOwned>(key: &str) -> Option<T> {
+    load_from(Location::Data, key)
+}
+
+pub fn save<T: Serialize>(key: &str, value: &T) {
+    save_to(Location::Data, key, value)
+}
+
+pub fn load_from<T: DeserializeOwned>(location: Location, key: &str) -> Option<T> {
+    parse(key, &read(location, key)?)
+}
+
+/// Parses the document stored under `key`, `None` if it can't be read
+pub fn parse<T: DeserializeOwned>(key: &str, content: &str) -> Option<T> {
+    match ron::de::from_str(content) {
+        Ok(v) => Some(v),
+        Err(e) => {
+            log::error!("Failed to parse stored {}: {}", key, e);
+            None
+        }
+    }
+}
+
+pub fn save_to<T: Serialize>(location: Location, key: &str, value: &T) {
+    let content = match ron::ser::to_string_pretty(value, Default::default()) {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to serialize {}: {}", key, e);
+            return;
+        }
+    };
+    write(location, key, &content);
+}
+
+/// Writes a file named `name` in `directory` of the data directory, returning its path. Files
+/// other than documents aren't stored on wasm
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(directory: &str, name: &str, content: &[u8]) -> Option<String> {
+    let directory = dirs::data_dir()?.join(APP_DIRECTORY).join(directory);
+    let path = directory.join(name);
+    let result = std::fs::create_dir_all(&directory).and_then(|_| std::fs::write(&path, content));
+    match result {
+        Ok(()) => Some(path.display().to_string()),
+        Err(e) => {
+            log::error!("Failed to write {}: {}", path.display(), e);
+            None
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(_directory: &str, name: &str, _content: &[u8]) -> Option<String> {
+    log::warn!("Can't write {} in a browser", name);
+    None
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path(location: Location, key: &str) -> Option<std::path::PathBuf> {
+    let directory = match location {
+        Location::Data => dirs::data_dir()?,
+        Location::Config => dirs::config_dir()?,
+    }
+    .join(APP_DIRECTORY);
+    Some(directory.join(format!("{}.ron", key)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read(location: Location, key: &str) -> Option<String> {
+    std::fs::read_to_string(path(location, key)?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(location: Location, key: &str, content: &str) {
+    let path = match path(location, key) {
+        Some(p) => p,
+        None => {
+            log::error!("No {:?} directory to store {} in", location, key);
+            return;
+        }
+    };
+    let result = path
+        .parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|_| std::fs::write(&path, content));
+    if let Err(e) = result {
+        log::error!("Failed to write {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read(_location: Location, key: &str) -> Option<String> {
+    local_storage()?
+        .get_item(&format!("{}.{}", APP_DIRECTORY, key))
+        .ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(_location: Location, key: &str, content: &str) {
+    let result = local_storage().map(|s| s.set_item(&format!("{}.{}", APP_DIRECTORY, key), content));
+    if !matches!(result, Some(Ok(()))) {
+        log::error!("Failed to write {} to the local storage", key);
+    }
+}
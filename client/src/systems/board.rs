@@ -1,44 +1,177 @@
+use bevy::app::AppExit;
+use bevy::asset::LoadState;
+use bevy::log;
 use bevy::prelude::*;
-use board_plugin::resources::{BoardOptions, BoardAssets, SpriteMaterial};
-use crate::resources::AppState;
+use board_plugin::events::HintRequestEvent;
+use board_plugin::resources::{
+    ActiveTheme, Board, BoardOptions, BoardAssets, InputLock, NextBoardSeed, NumberRule, Puzzle,
+};
+use crate::events::NewGameEvent;
+use crate::resources::{
+    ActiveLevel, AppState, DailyChallenge, GameTime, PuzzleProgress, ReplayPlayback, Settings,
+    Themes, skip_state_error,
+};
+use crate::cli::Launch;
+use crate::systems::{play_replay, HUD_HEIGHT};
+use crate::TITLE;
+
+/// Theme used when the chosen one fails to load
+pub const DEFAULT_THEME: &str = "Default";
 
 pub fn setup_board(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    launch: Res<Launch>,
+) {
+    let theme = launch.theme.as_deref().unwrap_or(&settings.theme);
+    commands.insert_resource(ActiveTheme(asset_server.load(&theme_path(theme))));
+    commands.insert_resource(Themes(asset_server.load("themes/index.themes.ron")));
+}
+
+/// Starts what was asked on the command line, or the menu, once the theme is loaded
+#[allow(clippy::too_many_arguments)]
+pub fn theme_loading(
     mut commands: Commands,
     mut state: ResMut<State<AppState>>,
     asset_server: Res<AssetServer>,
+    active_theme: Res<ActiveTheme>,
+    board_assets: Option<Res<BoardAssets>>,
+    settings: Res<Settings>,
+    launch: Res<Launch>,
+    mut fell_back: Local<bool>,
+    mut app_exit_ewr: EventWriter<AppExit>,
 ) {
-    commands.insert_resource(BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 100,
-        tile_padding: 1.0,
-        safe_start: true,
-        ..Default::default()
-    });
-
-    commands.insert_resource(BoardAssets {
-        label: "Default".to_string(),
-        board_material: SpriteMaterial {
-            color: Color::WHITE,
-            ..Default::default()
-        },
-        tile_material: SpriteMaterial {
-            color: Color::DARK_GRAY,
-            ..Default::default()
-        },
-        covered_tile_material: SpriteMaterial {
-            color: Color::GRAY,
-            ..Default::default()
-        },
-        bomb_counter_font: asset_server.load("fonts/pixeled.ttf"),
-        bomb_counter_colors: BoardAssets::default_colors(),
-        flag_material: SpriteMaterial {
-            color: Color::WHITE,
-            texture: asset_server.load("sprites/flag.png"),
-        },
-        bomb_material: SpriteMaterial {
-            color: Color::WHITE,
-            texture: asset_server.load("sprites/bomb.png"),
-        },
-    });
-    state.set(AppState::InGame).unwrap();
+    if board_assets.is_none() {
+        if asset_server.get_load_state(&active_theme.0) == LoadState::Failed {
+            if *fell_back {
+                log::error!("Failed to load the default theme, quitting");
+                app_exit_ewr.send(AppExit);
+            } else {
+                log::error!("Failed to load the theme, using the default one");
+                commands.insert_resource(ActiveTheme(asset_server.load(&theme_path(DEFAULT_THEME))));
+                *fell_back = true;
+            }
+        }
+        return;
+    }
+
+    let board_options = launch.board.clone().unwrap_or_else(|| settings.board.clone());
+    commands.insert_resource(game_options(&settings, board_options));
+    if let Some(puzzle) = &launch.puzzle {
+        commands.insert_resource(puzzle.clone());
+    }
+    if let Some(replay) = &launch.replay {
+        // Kept as the last game so it can be watched again
+        commands.insert_resource(replay.clone());
+        play_replay(&mut commands, replay.clone());
+    }
+    if launch.board.is_some() || launch.replay.is_some() {
+        skip_state_error(state.set(AppState::InGame));
+    } else {
+        skip_state_error(state.set(AppState::Menu));
+    }
+}
+
+/// Board options of a game with the player preferences and room for the HUD
+pub fn game_options(settings: &Settings, options: BoardOptions) -> BoardOptions {
+    BoardOptions {
+        top_margin: HUD_HEIGHT,
+        chording: settings.board.chording,
+        question_marks: settings.board.question_marks,
+        ..options
+    }
+}
+
+/// Asset path of a theme file
+pub fn theme_path(theme: &str) -> String {
+    format!("themes/{}.theme.ron", theme.to_lowercase())
+}
+
+/// Starts a new board from the current `BoardOptions`, leaving any menu or game over screen
+/// and stopping a replay
+pub fn start_game(commands: &mut Commands, state: &mut State<AppState>, game_time: &mut GameTime) {
+    let result = if state.current() == &AppState::InGame {
+        state.restart()
+    } else {
+        state.replace(AppState::InGame)
+    };
+    if result.is_err() {
+        skip_state_error(result);
+        return;
+    }
+    commands.remove_resource::<ReplayPlayback>();
+    commands.remove_resource::<InputLock>();
+    game_time.reset();
+    game_time.pause();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn new_game_handler(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut new_game_evr: EventReader<NewGameEvent>,
+    mut puzzle_progress: ResMut<PuzzleProgress>,
+    mut board_options: ResMut<BoardOptions>,
+    mut game_time: ResMut<GameTime>,
+    board: Option<Res<Board>>,
+    mut settings: ResMut<Settings>,
+) {
+    for event in new_game_evr.iter() {
+        match event {
+            NewGameEvent::Difficulty(difficulty) => {
+                log::info!("Starting {} game", difficulty.label());
+                puzzle_progress.current = None;
+                clear_game_mode(&mut commands);
+                difficulty.apply(&mut settings.board);
+                settings.save();
+                *board_options = game_options(&settings, settings.board.clone());
+            }
+            NewGameEvent::Random => {
+                puzzle_progress.current = None;
+                clear_game_mode(&mut commands);
+                *board_options = game_options(&settings, settings.board.clone());
+            }
+            NewGameEvent::Retry => match &board {
+                Some(board) => {
+                    log::info!("Retrying board {}", board.seed);
+                    commands.insert_resource(NextBoardSeed(board.seed));
+                }
+                None => continue,
+            },
+            _ => continue,
+        }
+        start_game(&mut commands, &mut state, &mut game_time);
+        return;
+    }
+}
+
+/// Shows the number rule of the current board in the window title
+pub fn number_rule_indicator(board: Option<Res<Board>>, mut windows: ResMut<Windows>) {
+    let board = match board {
+        Some(b) if b.is_added() => b,
+        _ => return,
+    };
+    let window = windows.get_primary_mut().unwrap();
+    match board.tile_map.number_rule {
+        NumberRule::Classic => window.set_title(TITLE.to_string()),
+        rule => window.set_title(format!("{} - {} numbers", TITLE, rule.label())),
+    }
+}
+
+pub fn hint_input_handler(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    mut hint_request_ewr: EventWriter<HintRequestEvent>,
+) {
+    if keys.just_pressed(settings.key_bindings.hint) {
+        hint_request_ewr.send(HintRequestEvent);
+    }
+}
+
+/// Leaves the puzzle, daily challenge or campaign level being played
+pub fn clear_game_mode(commands: &mut Commands) {
+    commands.remove_resource::<Puzzle>();
+    commands.remove_resource::<DailyChallenge>();
+    commands.remove_resource::<ActiveLevel>();
 }
@@ -0,0 +1,135 @@
+use crate::components::{LevelSelectScreen, MenuButton};
+use crate::events::NewGameEvent;
+use crate::resources::{
+    format_seconds, ActiveLevel, AppState, Campaign, CampaignProgress, GameStats, GameTime,
+    ReplayPlayback, Settings,
+};
+use crate::systems::ui::{
+    button_bundle, screen_bundle, text_bundle, Disabled, BUTTON_COLOR, DISABLED_BUTTON_COLOR,
+};
+use crate::systems::{clear_game_mode, game_options, start_game};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::BoardCompletedEvent;
+use board_plugin::resources::{BoardAssets, BoardOptions};
+
+pub fn load_campaign(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(CampaignProgress::load(
+        asset_server.load("levels.campaign.ron"),
+    ));
+}
+
+/// Spawns the level list once the campaign is loaded
+pub fn level_select_screen(
+    mut commands: Commands,
+    screen: Query<Entity, With<LevelSelectScreen>>,
+    progress: Res<CampaignProgress>,
+    campaigns: Res<Assets<Campaign>>,
+    board_assets: Res<BoardAssets>,
+) {
+    if !screen.is_empty() {
+        return;
+    }
+    let campaign = match campaigns.get(&progress.campaign) {
+        Some(c) => c,
+        None => return,
+    };
+    let font = board_assets.bomb_counter_font.clone();
+
+    commands
+        .spawn_bundle(screen_bundle())
+        .insert(LevelSelectScreen)
+        .insert(Name::new("LevelSelectScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle(campaign.name.clone(), font.clone(), 40.));
+            for (index, level) in campaign.levels.iter().enumerate() {
+                let unlocked = progress.is_unlocked(campaign, index);
+                let label = match (unlocked, progress.get(level)) {
+                    (false, _) => format!("{}. locked", index + 1),
+                    (true, None) => format!("{}. {}", index + 1, level.name),
+                    (true, Some(p)) => format!(
+                        "{}. {} {:<3} {}",
+                        index + 1,
+                        level.name,
+                        "*".repeat(p.stars as usize),
+                        format_seconds(p.best_time)
+                    ),
+                };
+                let color = if unlocked { BUTTON_COLOR } else { DISABLED_BUTTON_COLOR };
+                let mut button = parent.spawn_bundle(button_bundle(500., color));
+                button
+                    .insert(MenuButton::Level(index))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(text_bundle(label, font.clone(), 16.));
+                    });
+                if !unlocked {
+                    button.insert(Disabled);
+                }
+            }
+            parent
+                .spawn_bundle(button_bundle(500., BUTTON_COLOR))
+                .insert(MenuButton::Back)
+                .with_children(|parent| {
+                    parent.spawn_bundle(text_bundle("Back", font.clone(), 16.));
+                });
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn level_handler(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut new_game_evr: EventReader<NewGameEvent>,
+    progress: Res<CampaignProgress>,
+    campaigns: Res<Assets<Campaign>>,
+    mut board_options: ResMut<BoardOptions>,
+    mut game_time: ResMut<GameTime>,
+    settings: Res<Settings>,
+) {
+    let index = match new_game_evr.iter().find_map(|e| match e {
+        NewGameEvent::Level(index) => Some(*index),
+        _ => None,
+    }) {
+        Some(i) => i,
+        None => return,
+    };
+    let level = match campaigns
+        .get(&progress.campaign)
+        .and_then(|c| c.levels.get(index))
+    {
+        Some(l) => l,
+        None => return,
+    };
+    log::info!("Loading level {}", level.name);
+    clear_game_mode(&mut commands);
+    *board_options = game_options(&settings, level.options.clone());
+    commands.insert_resource(ActiveLevel(index));
+    start_game(&mut commands, &mut state, &mut game_time);
+}
+
+pub fn campaign_completion_system(
+    active_level: Option<Res<ActiveLevel>>,
+    playback: Option<Res<ReplayPlayback>>,
+    mut progress: ResMut<CampaignProgress>,
+    campaigns: Res<Assets<Campaign>>,
+    game_time: Res<GameTime>,
+    game_stats: Res<GameStats>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    if board_completed_evr.iter().count() == 0 || playback.is_some() {
+        return;
+    }
+    let (active_level, campaign) = match (active_level, campaigns.get(&progress.campaign)) {
+        (Some(l), Some(c)) => (l, c),
+        _ => return,
+    };
+    let level = match campaign.levels.get(active_level.0) {
+        Some(l) => l.clone(),
+        None => return,
+    };
+    let time = game_time.elapsed_secs();
+    let stars = level.stars(time, game_stats.hints > 0);
+    log::info!("Level {} cleared in {} ({} stars)", level.name, format_seconds(time), stars);
+    progress.record(&level, time, stars);
+    progress.save();
+}
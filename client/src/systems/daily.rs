@@ -0,0 +1,71 @@
+use crate::events::{GameTimeoutEvent, NewGameEvent};
+use crate::resources::{
+    AppState, DailyChallenge, DailyResult, DailyResults, GameStats, GameTime, Settings,
+};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::{BoardCompletedEvent, BombExplosionEvent};
+use crate::systems::{clear_game_mode, game_options, start_game};
+use board_plugin::resources::{Board, BoardOptions};
+
+pub fn load_daily_results(mut commands: Commands) {
+    commands.insert_resource(DailyResults::load());
+}
+
+pub fn daily_handler(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut new_game_evr: EventReader<NewGameEvent>,
+    mut game_time: ResMut<GameTime>,
+    daily_results: Res<DailyResults>,
+    mut board_options: ResMut<BoardOptions>,
+    settings: Res<Settings>,
+) {
+    if !new_game_evr.iter().any(|e| matches!(e, NewGameEvent::Daily)) {
+        return;
+    }
+    let daily = DailyChallenge::today();
+    if let Some(result) = daily_results.get(daily.date) {
+        log::info!(
+            "Daily challenge already played: {}",
+            result.share_string(daily.date, daily_results.streak(daily.date))
+        );
+    }
+    log::info!("Loading daily challenge of {}", daily.date);
+    clear_game_mode(&mut commands);
+    *board_options = game_options(&settings, daily.board_options());
+    commands.insert_resource(daily);
+    start_game(&mut commands, &mut state, &mut game_time);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn daily_result_system(
+    daily: Option<ResMut<DailyChallenge>>,
+    mut daily_results: ResMut<DailyResults>,
+    board: Option<Res<Board>>,
+    game_time: Res<GameTime>,
+    game_stats: Res<GameStats>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    mut game_timeout_evr: EventReader<GameTimeoutEvent>,
+) {
+    let won = board_completed_evr.iter().count() > 0;
+    let lost = bomb_explosion_evr.iter().count() > 0 || game_timeout_evr.iter().count() > 0;
+    let (mut daily, board) = match (daily, board) {
+        (Some(d), Some(b)) if !d.recorded && (won || lost) => (d, b),
+        _ => return,
+    };
+    daily.recorded = true;
+    let result = DailyResult {
+        won: won && !lost,
+        time: game_time.elapsed_secs(),
+        bbbv: board.tile_map.bbbv(),
+        hints_used: game_stats.hints > 0,
+    };
+    daily_results.record(daily.date, result.clone());
+    daily_results.save();
+    log::info!(
+        "{}",
+        result.share_string(daily.date, daily_results.streak(daily.date))
+    );
+}
@@ -0,0 +1,161 @@
+use crate::components::{GameEndScreen, MenuButton};
+use crate::events::GameTimeoutEvent;
+use crate::resources::{
+    format_seconds, ActiveLevel, AppState, BestTime, BestTimes, DailyChallenge, Difficulty,
+    GameConditions, GameOutcome, GameStats, GameSummary, GameTime, Profile, Replay, ReplayPlayback,
+};
+use crate::storage;
+use crate::systems::ui::{button_bundle, panel_bundle, screen_bundle, text_bundle, BUTTON_COLOR};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::{BoardCompletedEvent, BombExplosionEvent};
+use board_plugin::resources::{Board, BoardAssets, Puzzle};
+use chrono::Utc;
+
+pub fn load_best_times(mut commands: Commands) {
+    commands.insert_resource(BestTimes::load());
+    // Written back so the name can be changed in the stored profile
+    let profile = Profile::load();
+    profile.save();
+    commands.insert_resource(profile);
+}
+
+/// Ends the game on an explosion, a timeout or a cleared board, keeping the board under the
+/// game over or victory screen
+#[allow(clippy::too_many_arguments)]
+pub fn game_end_system(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    board: Option<Res<Board>>,
+    game_time: Res<GameTime>,
+    game_stats: Res<GameStats>,
+    mut best_times: ResMut<BestTimes>,
+    profile: Res<Profile>,
+    puzzle: Option<Res<Puzzle>>,
+    daily: Option<Res<DailyChallenge>>,
+    level: Option<Res<ActiveLevel>>,
+    playback: Option<Res<ReplayPlayback>>,
+    replay: Option<Res<Replay>>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    mut game_timeout_evr: EventReader<GameTimeoutEvent>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    let outcome = if bomb_explosion_evr.iter().count() > 0 {
+        GameOutcome::Exploded
+    } else if game_timeout_evr.iter().count() > 0 {
+        GameOutcome::TimedOut
+    } else if board_completed_evr.iter().count() > 0 {
+        GameOutcome::Won
+    } else {
+        return;
+    };
+    let board = match board {
+        Some(b) if state.current() == &AppState::InGame => b,
+        _ => return,
+    };
+    log::info!("Game over: {:?}", outcome);
+
+    let difficulty = Difficulty::from_board(
+        (board.tile_map.width, board.tile_map.height),
+        board.tile_map.bomb_count,
+    );
+    let eligible = GameConditions {
+        number_rule: board.tile_map.number_rule,
+        countdown: game_time.remaining().is_some(),
+        seed_chosen: board.seed_chosen,
+        special_mode: puzzle.is_some() || daily.is_some() || level.is_some() || playback.is_some(),
+        hints: game_stats.hints,
+    }
+    .competes_for_best_times();
+    let mut summary = GameSummary {
+        outcome,
+        time: game_time.elapsed_secs(),
+        bbbv: board.tile_map.bbbv(),
+        clicks: game_stats.clicks,
+        previous_best: best_times.best(&difficulty).filter(|_| eligible),
+        personal_best: false,
+    };
+    if outcome == GameOutcome::Won && eligible {
+        summary.personal_best = best_times.record(
+            &difficulty,
+            BestTime {
+                time: summary.time,
+                date: Utc::now().date_naive(),
+                bbbv_per_second: summary.bbbv_per_second(),
+                efficiency: summary.efficiency(),
+                profile: profile.name.clone(),
+                no_flags: game_stats.flags == 0,
+            },
+        );
+        best_times.save();
+    }
+    commands.insert_resource(summary);
+    if let (Some(replay), None) = (replay, &playback) {
+        // Can be played again with `--replay`
+        storage::save("last_replay", &*replay);
+    }
+    let next = match outcome {
+        GameOutcome::Won => AppState::Won,
+        GameOutcome::Exploded | GameOutcome::TimedOut => AppState::GameOver,
+    };
+    if let Err(e) = state.push(next) {
+        log::error!("Failed to end the game: {:?}", e);
+    }
+}
+
+pub fn game_end_screen(
+    mut commands: Commands,
+    summary: Res<GameSummary>,
+    board_assets: Res<BoardAssets>,
+) {
+    let font = &board_assets.bomb_counter_font;
+    let title = match summary.outcome {
+        GameOutcome::Won => "Cleared!",
+        GameOutcome::Exploded => "Boom!",
+        GameOutcome::TimedOut => "Time's up!",
+    };
+    let best = if summary.personal_best {
+        "New personal best!".to_string()
+    } else {
+        match summary.previous_best {
+            Some(best) => format!("Personal best {}", format_seconds(best)),
+            None => String::new(),
+        }
+    };
+    let lines = [
+        format!("Time {}", format_seconds(summary.time)),
+        format!("3BV {} - 3BV/s {:.2}", summary.bbbv, summary.bbbv_per_second()),
+        format!("Clicks {} - Efficiency {:.0}%", summary.clicks, summary.efficiency()),
+        best,
+    ];
+    let buttons = [
+        (MenuButton::Retry, "Retry same board"),
+        (MenuButton::NewBoard, "New game"),
+        (MenuButton::WatchReplay, "Watch replay"),
+        (MenuButton::MainMenu, "Menu"),
+    ];
+
+    commands
+        .spawn_bundle(NodeBundle {
+            color: Color::NONE.into(),
+            ..screen_bundle()
+        })
+        .insert(GameEndScreen)
+        .insert(Name::new("GameEndScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(panel_bundle()).with_children(|parent| {
+                parent.spawn_bundle(text_bundle(title, font.clone(), 40.));
+                for line in lines {
+                    parent.spawn_bundle(text_bundle(line, font.clone(), 16.));
+                }
+                for (button, label) in buttons {
+                    parent
+                        .spawn_bundle(button_bundle(300., BUTTON_COLOR))
+                        .insert(button)
+                        .with_children(|parent| {
+                            parent.spawn_bundle(text_bundle(label, font.clone(), 16.));
+                        });
+                }
+            });
+        });
+}
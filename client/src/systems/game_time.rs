@@ -1,22 +1,62 @@
-use crate::resources::GameTime;
+use crate::events::GameTimeoutEvent;
+use crate::resources::{CountdownOptions, GameTime};
 use bevy::log;
 use bevy::prelude::*;
-use board_plugin::events::{TileMarkEvent, TileTriggerEvent};
+use board_plugin::events::{OpeningClearedEvent, TileMarkEvent, TileTriggerEvent};
+use board_plugin::resources::Board;
 
 pub fn game_time_system(
     mut game_time: ResMut<GameTime>,
     time: Res<Time>,
     tile_trigger_evr: EventReader<TileTriggerEvent>,
     tile_mark_trigger_evr: EventReader<TileMarkEvent>,
+    mut game_timeout_ewr: EventWriter<GameTimeoutEvent>,
 ) {
-    if game_time.paused() {
-        if tile_trigger_evr.len() > 0 || tile_mark_trigger_evr.len() > 0 {
-            log::info!("Game started");
-            game_time.unpause();
-        }
+    if game_time.paused() && (!tile_trigger_evr.is_empty() || !tile_mark_trigger_evr.is_empty()) {
+        log::info!("Game started");
+        game_time.unpause();
     }
 
     if !game_time.paused() {
         game_time.tick(time.delta());
+        if game_time.is_timed_out() {
+            log::info!("Time is up");
+            game_time.pause();
+            game_timeout_ewr.send(GameTimeoutEvent);
+        }
+    }
+}
+
+pub fn countdown_setup_system(
+    board: Option<Res<Board>>,
+    countdown_options: Res<CountdownOptions>,
+    mut game_time: ResMut<GameTime>,
+) {
+    let board = match board {
+        Some(b) if b.is_added() => b,
+        _ => return,
+    };
+    if !countdown_options.enabled {
+        return;
+    }
+    let tile_map = &board.tile_map;
+    let safe_tiles = tile_map.width as u32 * tile_map.height as u32 - tile_map.bomb_count as u32;
+    let budget = countdown_options
+        .budget
+        .budget(safe_tiles, tile_map.bbbv());
+    log::info!("Countdown from {:?}", budget);
+    game_time.start_countdown(budget);
+}
+
+pub fn opening_bonus_system(
+    countdown_options: Res<CountdownOptions>,
+    mut game_time: ResMut<GameTime>,
+    mut opening_cleared_evr: EventReader<OpeningClearedEvent>,
+) {
+    for _ in opening_cleared_evr.iter() {
+        // Openings uncovered before the first click, like the safe start, earn nothing
+        if !game_time.paused() {
+            game_time.add_bonus(countdown_options.opening_bonus);
+        }
     }
 }
@@ -0,0 +1,147 @@
+use crate::components::{Hud, HudModeText, HudTimeText, MineCounterText, ResetButton, ResetFaceText};
+use crate::resources::{AppState, GameTime};
+use crate::systems::start_game;
+use crate::systems::ui::{button_bundle, text_bundle, BUTTON_COLOR};
+use bevy::prelude::*;
+use board_plugin::resources::{Board, BoardAssets, NumberRule, TilePress};
+
+/// Window height taken by the HUD, kept free by the board layout
+pub const HUD_HEIGHT: f32 = 60.;
+
+/// Spawns the HUD along with a board and removes it with the board
+pub fn hud_system(
+    mut commands: Commands,
+    hud: Query<Entity, With<Hud>>,
+    board: Option<Res<Board>>,
+    board_assets: Res<BoardAssets>,
+) {
+    match (board.is_some(), hud.get_single()) {
+        (true, Err(_)) => spawn_hud(&mut commands, &board_assets),
+        (false, Ok(entity)) => commands.entity(entity).despawn_recursive(),
+        _ => (),
+    }
+}
+
+fn spawn_hud(commands: &mut Commands, board_assets: &BoardAssets) {
+    let font = board_assets.bomb_counter_font.clone();
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(0.),
+                    left: Val::Px(0.),
+                    ..Default::default()
+                },
+                size: Size::new(Val::Percent(100.), Val::Px(HUD_HEIGHT)),
+                padding: Rect::all(Val::Px(8.)),
+                justify_content: JustifyContent::SpaceBetween,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: Color::rgb(0.1, 0.1, 0.1).into(),
+            ..Default::default()
+        })
+        .insert(Hud)
+        .insert(Name::new("Hud"))
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(text_bundle("", font.clone(), 24.))
+                .insert(MineCounterText);
+            parent
+                .spawn_bundle(button_bundle(HUD_HEIGHT, BUTTON_COLOR))
+                .insert(ResetButton)
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(text_bundle("", font.clone(), 16.))
+                        .insert(ResetFaceText);
+                });
+            parent
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        flex_direction: FlexDirection::ColumnReverse,
+                        align_items: AlignItems::FlexEnd,
+                        ..Default::default()
+                    },
+                    color: Color::NONE.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn_bundle(text_bundle("", font.clone(), 24.))
+                        .insert(HudTimeText);
+                    parent
+                        .spawn_bundle(text_bundle("", font, 10.))
+                        .insert(HudModeText);
+                });
+        });
+}
+
+/// Writes the value of a HUD text when it changed, a written text being laid out again
+fn set_text(text: &mut Mut<Text>, value: &str) {
+    if text.sections[0].value != value {
+        text.sections[0].value = value.to_string();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn hud_text_system(
+    board: Option<Res<Board>>,
+    game_time: Res<GameTime>,
+    state: Res<State<AppState>>,
+    tile_press: Res<TilePress>,
+    mut texts: ParamSet<(
+        Query<&mut Text, With<MineCounterText>>,
+        Query<&mut Text, With<HudTimeText>>,
+        Query<&mut Text, With<HudModeText>>,
+        Query<&mut Text, With<ResetFaceText>>,
+    )>,
+) {
+    let board = match board {
+        Some(b) => b,
+        None => return,
+    };
+    let mines_left = board.tile_map.bomb_count as i32 - board.flag_count() as i32;
+    for mut text in texts.p0().iter_mut() {
+        set_text(&mut text, &format!("{:03}", mines_left));
+    }
+    for mut text in texts.p1().iter_mut() {
+        set_text(&mut text, &game_time.to_string());
+    }
+    let mut mode = Vec::new();
+    if board.tile_map.number_rule != NumberRule::Classic {
+        mode.push(format!("{} numbers", board.tile_map.number_rule.label()));
+    }
+    if game_time.remaining().is_some() {
+        mode.push("countdown".to_string());
+    }
+    for mut text in texts.p2().iter_mut() {
+        set_text(&mut text, &mode.join(" - "));
+    }
+    let face = if state.current() == &AppState::Won {
+        "B)"
+    } else if state.current() == &AppState::GameOver {
+        "X("
+    } else if state.current() == &AppState::InGame && !tile_press.pressed_tiles(&board).is_empty() {
+        ":O"
+    } else {
+        ":)"
+    };
+    for mut text in texts.p3().iter_mut() {
+        set_text(&mut text, face);
+    }
+}
+
+pub fn reset_button_action(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<ResetButton>)>,
+    mut game_time: ResMut<GameTime>,
+) {
+    if !matches!(state.current(), AppState::InGame | AppState::GameOver | AppState::Won) {
+        return;
+    }
+    if buttons.iter().any(|i| *i == Interaction::Clicked) {
+        start_game(&mut commands, &mut state, &mut game_time);
+    }
+}
@@ -0,0 +1,267 @@
+use crate::components::{
+    BestTimesScreen, CustomFieldText, CustomGameErrorText, CustomGameScreen, MenuButton,
+    MenuScreen,
+};
+use crate::events::NewGameEvent;
+use crate::resources::{
+    AppState, BestTimes, BestTimesView, CustomField, CustomGameForm, Difficulty, skip_state_error,
+};
+use crate::systems::ui::{
+    button_bundle, row_bundle, screen_bundle, text_bundle, ActiveButtons, Disabled, BUTTON_COLOR,
+    DISABLED_BUTTON_COLOR,
+};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::window::ReceivedCharacter;
+use board_plugin::resources::BoardAssets;
+
+pub(super) const MENU_WIDTH: f32 = 400.;
+
+pub(super) fn spawn_button(parent: &mut ChildBuilder, button: MenuButton, label: &str, font: &Handle<Font>) {
+    parent
+        .spawn_bundle(button_bundle(MENU_WIDTH, BUTTON_COLOR))
+        .insert(button)
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle(label, font.clone(), 16.));
+        });
+}
+
+pub fn menu_screen(
+    mut commands: Commands,
+    screen: Query<Entity, With<MenuScreen>>,
+    state: Res<State<AppState>>,
+    board_assets: Res<BoardAssets>,
+) {
+    if !screen.is_empty() {
+        return;
+    }
+    let font = &board_assets.bomb_counter_font;
+    let can_continue = state.inactives().contains(&AppState::InGame);
+
+    commands
+        .spawn_bundle(screen_bundle())
+        .insert(MenuScreen)
+        .insert(Name::new("MenuScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle(crate::TITLE, font.clone(), 40.));
+            if can_continue {
+                spawn_button(parent, MenuButton::Continue, "Continue", font);
+            } else {
+                parent
+                    .spawn_bundle(button_bundle(MENU_WIDTH, DISABLED_BUTTON_COLOR))
+                    .insert(Disabled)
+                    .with_children(|parent| {
+                        parent.spawn_bundle(text_bundle("Continue", font.clone(), 16.));
+                    });
+            }
+            for difficulty in Difficulty::PRESETS {
+                spawn_button(parent, MenuButton::NewGame(difficulty), &difficulty.label(), font);
+            }
+            spawn_button(parent, MenuButton::CustomGame, "Custom", font);
+            spawn_button(parent, MenuButton::Daily, "Daily", font);
+            spawn_button(parent, MenuButton::Puzzles, "Puzzles", font);
+            spawn_button(parent, MenuButton::Campaign, "Campaign", font);
+            spawn_button(parent, MenuButton::BestTimes, "Best times", font);
+            spawn_button(parent, MenuButton::Settings, "Settings", font);
+            spawn_button(parent, MenuButton::Quit, "Quit", font);
+        });
+}
+
+pub fn custom_game_screen(
+    mut commands: Commands,
+    screen: Query<Entity, With<CustomGameScreen>>,
+    board_assets: Res<BoardAssets>,
+) {
+    if !screen.is_empty() {
+        return;
+    }
+    let font = &board_assets.bomb_counter_font;
+
+    commands
+        .spawn_bundle(screen_bundle())
+        .insert(CustomGameScreen)
+        .insert(Name::new("CustomGameScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle("Custom", font.clone(), 40.));
+            for field in CustomField::ALL {
+                parent.spawn_bundle(row_bundle()).with_children(|parent| {
+                    parent.spawn_bundle(text_bundle(field.label(), font.clone(), 16.));
+                    parent
+                        .spawn_bundle(button_bundle(MENU_WIDTH / 2., BUTTON_COLOR))
+                        .insert(MenuButton::FocusField(field))
+                        .with_children(|parent| {
+                            parent
+                                .spawn_bundle(text_bundle("", font.clone(), 16.))
+                                .insert(CustomFieldText(field));
+                        });
+                });
+            }
+            parent
+                .spawn_bundle(text_bundle("", font.clone(), 12.))
+                .insert(CustomGameErrorText);
+            spawn_button(parent, MenuButton::StartCustomGame, "Start", font);
+            spawn_button(parent, MenuButton::Back, "Back", font);
+        });
+}
+
+pub fn best_times_screen(
+    mut commands: Commands,
+    screen: Query<Entity, With<BestTimesScreen>>,
+    board_assets: Res<BoardAssets>,
+    best_times: Res<BestTimes>,
+    view: Res<BestTimesView>,
+) {
+    if !screen.is_empty() {
+        return;
+    }
+    let font = &board_assets.bomb_counter_font;
+    let difficulties = best_times.difficulties();
+    let difficulty = &difficulties[view.difficulty % difficulties.len()];
+    let table = best_times.sorted(difficulty, view.sort);
+
+    commands
+        .spawn_bundle(screen_bundle())
+        .insert(BestTimesScreen)
+        .insert(Name::new("BestTimesScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle("Best times", font.clone(), 40.));
+            spawn_button(parent, MenuButton::CycleBestTimesDifficulty, difficulty, font);
+            spawn_button(
+                parent,
+                MenuButton::CycleBestTimesSort,
+                &format!("Sort by {}", view.sort.label()),
+                font,
+            );
+            if table.is_empty() {
+                parent.spawn_bundle(text_bundle("No times yet", font.clone(), 12.));
+            }
+            for (i, entry) in table.iter().enumerate() {
+                parent.spawn_bundle(text_bundle(entry.row(i + 1), font.clone(), 12.));
+            }
+            spawn_button(parent, MenuButton::Back, "Back", font);
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn menu_action(
+    mut state: ResMut<State<AppState>>,
+    buttons: Query<(&Interaction, &MenuButton), ActiveButtons>,
+    best_times_screen: Query<Entity, With<BestTimesScreen>>,
+    mut commands: Commands,
+    mut new_game_ewr: EventWriter<NewGameEvent>,
+    mut app_exit_ewr: EventWriter<AppExit>,
+    mut form: ResMut<CustomGameForm>,
+    mut best_times_view: ResMut<BestTimesView>,
+    best_times: Res<BestTimes>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        match *button {
+            MenuButton::Continue => {
+                skip_state_error(state.pop());
+            }
+            MenuButton::NewGame(difficulty) => new_game_ewr.send(NewGameEvent::Difficulty(difficulty)),
+            MenuButton::CustomGame => {
+                form.error = None;
+                skip_state_error(state.set(AppState::CustomGame));
+            }
+            MenuButton::FocusField(field) => form.focused = field,
+            MenuButton::StartCustomGame => {
+                match Difficulty::custom(&form.width, &form.height, &form.bomb_count) {
+                    Ok(difficulty) => new_game_ewr.send(NewGameEvent::Difficulty(difficulty)),
+                    Err(e) => form.error = Some(e.to_string()),
+                }
+            }
+            MenuButton::Daily => new_game_ewr.send(NewGameEvent::Daily),
+            MenuButton::Puzzles => new_game_ewr.send(NewGameEvent::NextPuzzle),
+            MenuButton::Campaign => {
+                skip_state_error(state.set(AppState::LevelSelect));
+            }
+            MenuButton::Level(index) => new_game_ewr.send(NewGameEvent::Level(index)),
+            MenuButton::Settings => {
+                skip_state_error(state.set(AppState::Settings));
+            }
+            MenuButton::BestTimes => {
+                skip_state_error(state.set(AppState::BestTimes));
+            }
+            MenuButton::CycleBestTimesDifficulty => {
+                let count = best_times.difficulties().len();
+                best_times_view.difficulty = (best_times_view.difficulty + 1) % count;
+            }
+            MenuButton::CycleBestTimesSort => best_times_view.sort = best_times_view.sort.next(),
+            MenuButton::Back => {
+                skip_state_error(state.set(AppState::Menu));
+            }
+            MenuButton::Retry => new_game_ewr.send(NewGameEvent::Retry),
+            MenuButton::NewBoard => new_game_ewr.send(NewGameEvent::Random),
+            MenuButton::WatchReplay => new_game_ewr.send(NewGameEvent::Replay),
+            MenuButton::MainMenu => {
+                skip_state_error(state.replace(AppState::Menu));
+            }
+            MenuButton::Quit => app_exit_ewr.send(AppExit),
+            // Handled by `settings_action`
+            MenuButton::ToggleSafeStart
+            | MenuButton::ToggleChording
+            | MenuButton::ToggleQuestionMarks
+            | MenuButton::CycleNumberRule
+            | MenuButton::CycleCountdown
+            | MenuButton::CycleTheme
+            | MenuButton::CycleWindowSize
+            | MenuButton::CyclePanButton
+            | MenuButton::ToggleReduceMotion
+            | MenuButton::CyclePalette
+            | MenuButton::CycleNumberCue
+            | MenuButton::CycleSound
+            | MenuButton::Rebind(_) => (),
+        }
+        // The best times screen is rebuilt with the new view
+        if matches!(
+            button,
+            MenuButton::CycleBestTimesDifficulty | MenuButton::CycleBestTimesSort
+        ) {
+            for entity in best_times_screen.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        return;
+    }
+}
+
+pub fn custom_game_input(
+    keys: Res<Input<KeyCode>>,
+    mut received_character_evr: EventReader<ReceivedCharacter>,
+    mut form: ResMut<CustomGameForm>,
+    mut new_game_ewr: EventWriter<NewGameEvent>,
+) {
+    for event in received_character_evr.iter() {
+        form.push(event.char);
+    }
+    if keys.just_pressed(KeyCode::Back) {
+        form.pop();
+    }
+    if keys.just_pressed(KeyCode::Tab) {
+        form.focused = form.focused.next();
+    }
+    if keys.just_pressed(KeyCode::Return) {
+        match Difficulty::custom(&form.width, &form.height, &form.bomb_count) {
+            Ok(difficulty) => new_game_ewr.send(NewGameEvent::Difficulty(difficulty)),
+            Err(e) => form.error = Some(e.to_string()),
+        }
+    }
+}
+
+pub fn custom_game_text(
+    form: Res<CustomGameForm>,
+    mut field_texts: Query<(&mut Text, &CustomFieldText), Without<CustomGameErrorText>>,
+    mut error_texts: Query<&mut Text, With<CustomGameErrorText>>,
+) {
+    for (mut text, field) in field_texts.iter_mut() {
+        let cursor = if form.focused == field.0 { "_" } else { "" };
+        text.sections[0].value = format!("{}{}", form.value(field.0), cursor);
+    }
+    for mut text in error_texts.iter_mut() {
+        text.sections[0].value = form.error.clone().unwrap_or_default();
+    }
+}
@@ -1,7 +1,32 @@
 mod pause;
 mod game_time;
 mod board;
+mod puzzle;
+mod daily;
+mod stats;
+mod campaign;
+mod menu;
+mod hud;
+mod replay;
+mod game_end;
+mod settings;
+#[cfg(feature = "audio")]
+mod sound;
+mod screenshot;
+pub mod ui;
 
 pub use pause::*;
 pub use game_time::*;
-pub use board::*;
\ No newline at end of file
+pub use board::*;
+pub use puzzle::*;
+pub use daily::*;
+pub use stats::*;
+pub use campaign::*;
+pub use menu::*;
+pub use hud::*;
+pub use replay::*;
+pub use game_end::*;
+pub use settings::*;
+#[cfg(feature = "audio")]
+pub use sound::*;
+pub use screenshot::*;
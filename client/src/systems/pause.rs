@@ -1,5 +1,5 @@
-use bevy::{prelude::*, math::vec2};
-use board_plugin::resources::BoardAssets;
+use bevy::{prelude::*, math::vec2, render::camera::Camera2d};
+use board_plugin::resources::{Board, BoardAssets, NumberRule};
 
 use crate::{resources::GameTime, components::PauseScreen};
 
@@ -8,8 +8,12 @@ pub fn pause_screen(
     windows: Res<Windows>,
     game_time: ResMut<GameTime>,
     board_assets: Res<BoardAssets>,
+    board: Res<Board>,
+    cameras: Query<&Transform, With<Camera2d>>,
 ) {
     let window = windows.get_primary().unwrap();
+    // Covers the window wherever the board camera was zoomed and panned to
+    let camera = cameras.get_single().copied().unwrap_or_default();
 
     commands
         .spawn_bundle(SpriteBundle {
@@ -18,22 +22,35 @@ pub fn pause_screen(
                 custom_size: Some(vec2(window.width(), window.height())),
                 ..Default::default()
             },
-            transform: Transform::from_xyz(0., 0., 4.),
+            transform: Transform::from_xyz(camera.translation.x, camera.translation.y, 4.)
+                .with_scale(camera.scale),
             ..Default::default()
         })
         .insert(PauseScreen)
         .insert(Name::new("PauseScreen"))
         .with_children(|parent| {
+            let time_left = if game_time.remaining().is_some() { " left" } else { "" };
+            let mut sections = vec![TextSection {
+                value: format!("PAUSED - {}{}", *game_time, time_left),
+                style: TextStyle {
+                    font: board_assets.bomb_counter_font.clone(),
+                    font_size: 100.,
+                    color: Color::WHITE,
+                },
+            }];
+            if board.tile_map.number_rule != NumberRule::Classic {
+                sections.push(TextSection {
+                    value: format!("\n{} numbers", board.tile_map.number_rule.label()),
+                    style: TextStyle {
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: 40.,
+                        color: Color::WHITE,
+                    },
+                });
+            }
             parent.spawn_bundle(Text2dBundle {
                 text: Text {
-                    sections: vec![TextSection {
-                        value: format!("PAUSED - {}", game_time.to_string()),
-                        style: TextStyle {
-                            font: board_assets.bomb_counter_font.clone(),
-                            font_size: 100.,
-                            color: Color::WHITE,
-                        },
-                    }],
+                    sections,
                     alignment: TextAlignment {
                         vertical: VerticalAlign::Center,
                         horizontal: HorizontalAlign::Center,
@@ -0,0 +1,65 @@
+use crate::events::NewGameEvent;
+use crate::resources::{AppState, GameTime, PuzzleProgress, Settings};
+use crate::systems::{clear_game_mode, game_options, start_game};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::BoardCompletedEvent;
+use board_plugin::resources::{BoardOptions, Puzzle, PuzzlePack};
+
+pub fn load_puzzle_pack(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PuzzleProgress::new(
+        asset_server.load("puzzles/classic.pack.ron"),
+    ));
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn puzzle_handler(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut new_game_evr: EventReader<NewGameEvent>,
+    mut progress: ResMut<PuzzleProgress>,
+    packs: Res<Assets<PuzzlePack>>,
+    mut game_time: ResMut<GameTime>,
+    mut board_options: ResMut<BoardOptions>,
+    settings: Res<Settings>,
+) {
+    if !new_game_evr.iter().any(|e| matches!(e, NewGameEvent::NextPuzzle)) {
+        return;
+    }
+    let pack = match packs.get(&progress.pack) {
+        Some(p) => p,
+        None => {
+            log::warn!("Puzzle pack is not loaded yet");
+            return;
+        }
+    };
+    let index = match progress.next_puzzle(pack) {
+        Some(i) => i,
+        None => {
+            log::warn!("Puzzle pack {} is empty", pack.name);
+            return;
+        }
+    };
+    log::info!("loading puzzle {} of pack {}", index + 1, pack.name);
+    progress.current = Some(index);
+    clear_game_mode(&mut commands);
+    *board_options = game_options(&settings, settings.board.clone());
+    commands.insert_resource(pack.puzzles[index].clone());
+    start_game(&mut commands, &mut state, &mut game_time);
+}
+
+pub fn puzzle_completion_system(
+    mut progress: ResMut<PuzzleProgress>,
+    puzzle: Option<Res<Puzzle>>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    if board_completed_evr.iter().count() == 0 {
+        return;
+    }
+    if let Some(puzzle) = puzzle {
+        if !progress.is_completed(&puzzle.name) {
+            log::info!("Puzzle {} completed", puzzle.name);
+            progress.complete(&puzzle.name);
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::events::NewGameEvent;
+use crate::resources::{AppState, GameTime, Replay, ReplayAction, ReplayMove, ReplayPlayback};
+use crate::systems::{start_game, HUD_HEIGHT};
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::events::{
+    HintUsedEvent, TileMarkEvent, TileTriggerEvent, UncoverAdjacentTilesEvent,
+};
+use board_plugin::resources::{Board, BoardOptions, InputLock, NextBoardSeed, Puzzle};
+
+/// Records the moves of the current game, starting over with each board
+#[allow(clippy::too_many_arguments)]
+pub fn replay_recorder(
+    mut commands: Commands,
+    replay: Option<ResMut<Replay>>,
+    playback: Option<Res<ReplayPlayback>>,
+    board: Option<Res<Board>>,
+    board_options: Res<BoardOptions>,
+    puzzle: Option<Res<Puzzle>>,
+    game_time: Res<GameTime>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut uncover_adjacent_tiles_evr: EventReader<UncoverAdjacentTilesEvent>,
+    mut hint_used_evr: EventReader<HintUsedEvent>,
+) {
+    let actions: Vec<_> = tile_trigger_evr
+        .iter()
+        .map(|e| ReplayAction::Uncover(e.0))
+        .chain(tile_mark_evr.iter().map(|e| ReplayAction::Mark(e.0)))
+        .chain(uncover_adjacent_tiles_evr.iter().map(|e| ReplayAction::UncoverAdjacent(e.0)))
+        .chain(hint_used_evr.iter().map(|e| ReplayAction::Uncover(e.0)))
+        .collect();
+    if playback.is_some() {
+        return;
+    }
+    let board = match board {
+        Some(b) => b,
+        None => return,
+    };
+    if board.is_added() {
+        commands.insert_resource(Replay {
+            seed: board.seed,
+            options: board_options.clone(),
+            puzzle: puzzle.map(|p| p.clone()),
+            moves: Vec::new(),
+        });
+        return;
+    }
+    if let Some(mut replay) = replay {
+        let time = game_time.elapsed_secs();
+        replay
+            .moves
+            .extend(actions.into_iter().map(|action| ReplayMove { time, action }));
+    }
+}
+
+pub fn replay_handler(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut new_game_evr: EventReader<NewGameEvent>,
+    replay: Option<Res<Replay>>,
+    mut game_time: ResMut<GameTime>,
+) {
+    if !new_game_evr.iter().any(|e| matches!(e, NewGameEvent::Replay)) {
+        return;
+    }
+    let replay = match replay {
+        Some(r) => r.clone(),
+        None => {
+            log::warn!("No game to replay");
+            return;
+        }
+    };
+    start_game(&mut commands, &mut state, &mut game_time);
+    play_replay(&mut commands, replay);
+}
+
+/// Sets up the next board to play back `replay`
+pub fn play_replay(commands: &mut Commands, replay: Replay) {
+    log::info!("Replaying board {} ({} moves)", replay.seed, replay.moves.len());
+    // The recorded options are kept as they are, marking and chording must behave the same
+    commands.insert_resource(BoardOptions {
+        top_margin: HUD_HEIGHT,
+        ..replay.options.clone()
+    });
+    match &replay.puzzle {
+        Some(puzzle) => commands.insert_resource(puzzle.clone()),
+        None => commands.remove_resource::<Puzzle>(),
+    }
+    commands.insert_resource(NextBoardSeed(replay.seed));
+    commands.insert_resource(InputLock);
+    commands.insert_resource(ReplayPlayback::new(replay));
+}
+
+/// Sends the recorded moves as board events when their time comes
+pub fn replay_playback(
+    mut commands: Commands,
+    playback: Option<ResMut<ReplayPlayback>>,
+    time: Res<Time>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut uncover_adjacent_tiles_ewr: EventWriter<UncoverAdjacentTilesEvent>,
+) {
+    let mut playback = match playback {
+        Some(p) if !p.is_finished() => p,
+        _ => return,
+    };
+    playback.elapsed += time.delta_seconds();
+    while let Some(next) = playback.replay.moves.get(playback.next).copied() {
+        if next.time > playback.elapsed {
+            break;
+        }
+        match next.action {
+            ReplayAction::Uncover(c) => tile_trigger_ewr.send(TileTriggerEvent(c)),
+            ReplayAction::Mark(c) => tile_mark_ewr.send(TileMarkEvent(c)),
+            ReplayAction::UncoverAdjacent(c) => {
+                uncover_adjacent_tiles_ewr.send(UncoverAdjacentTilesEvent(c))
+            }
+        }
+        playback.next += 1;
+    }
+    if playback.is_finished() {
+        log::info!("Replay finished");
+        // The player can take over a replay that didn't end the game
+        commands.remove_resource::<InputLock>();
+    }
+}
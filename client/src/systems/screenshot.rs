@@ -0,0 +1,89 @@
+use crate::resources::{Rebinding, Replay, Settings};
+use crate::storage;
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::export::{board_png, BoardView, ExportOptions};
+use board_plugin::resources::{Board, BoardAssets, NumberStyle};
+
+/// Folder of the data directory board images are written to
+const SCREENSHOT_DIRECTORY: &str = "screenshots";
+/// Longest side of board images in pixels, big boards get smaller tiles
+const MAX_IMAGE_SIZE: u32 = 4096;
+
+/// Writes an image of the current board when the screenshot key is pressed, along with its
+/// replay to play the game again from its seed, which is in the file name (see
+/// [`BoardOptions::seed`](board_plugin::resources::BoardOptions::seed)). Holding shift draws the
+/// solution
+#[allow(clippy::too_many_arguments)]
+pub fn screenshot_system(
+    keys: Res<Input<KeyCode>>,
+    settings: Res<Settings>,
+    rebinding: Res<Rebinding>,
+    board: Option<Res<Board>>,
+    board_assets: Option<Res<BoardAssets>>,
+    number_style: Option<Res<NumberStyle>>,
+    replay: Option<Res<Replay>>,
+    images: Res<Assets<Image>>,
+    fonts: Res<Assets<Font>>,
+) {
+    if rebinding.action.is_some() || !keys.just_pressed(settings.key_bindings.screenshot) {
+        return;
+    }
+    let (board, board_assets) = match (board, board_assets) {
+        (Some(b), Some(a)) => (b, a),
+        _ => return,
+    };
+    let map = &board.tile_map;
+    let tile_size = match ExportOptions::fitting_tile_size(map.width, map.height, MAX_IMAGE_SIZE) {
+        Some(size) => size,
+        None => {
+            log::warn!(
+                "Board {}x{} is too big for an image of {} pixels a side",
+                map.width,
+                map.height,
+                MAX_IMAGE_SIZE
+            );
+            return;
+        }
+    };
+    let solution = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let options = ExportOptions {
+        view: if solution {
+            BoardView::Solution
+        } else {
+            BoardView::Player
+        },
+        tile_size,
+        style: number_style.map(|s| s.clone()).unwrap_or_default(),
+    };
+    let png = match board_png(&board, &options, &board_assets, Some(&images), Some(&fonts)) {
+        Ok(png) => png,
+        Err(e) => {
+            log::error!("Failed to encode the board image: {}", e);
+            return;
+        }
+    };
+
+    let name = format!(
+        "board-{}-{}{}",
+        board.seed,
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        if solution { "-solution" } else { "" }
+    );
+    if let Some(path) = storage::save_file(SCREENSHOT_DIRECTORY, &format!("{}.png", name), &png) {
+        log::info!("Saved board {} to {}", board.seed, path);
+    }
+    let replay = replay.filter(|r| r.seed == board.seed);
+    if let Some(replay) = replay {
+        match ron::ser::to_string_pretty(&*replay, Default::default()) {
+            Ok(content) => {
+                storage::save_file(
+                    SCREENSHOT_DIRECTORY,
+                    &format!("{}.replay.ron", name),
+                    content.as_bytes(),
+                );
+            }
+            Err(e) => log::error!("Failed to serialize the replay: {}", e),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use crate::components::{MenuButton, SettingsScreen};
+use crate::resources::{CountdownOptions, KeyAction, Rebinding, Settings, ThemeIndex, Themes};
+use crate::systems::menu::spawn_button;
+use crate::systems::ui::{screen_bundle, text_bundle, ActiveButtons};
+use crate::systems::theme_path;
+use bevy::log;
+use bevy::prelude::*;
+use board_plugin::resources::{
+    ActiveTheme, AnimationOptions, BoardAssets, BoardOptions, CameraControls, NumberStyle,
+};
+
+pub fn settings_screen(
+    mut commands: Commands,
+    screen: Query<Entity, With<SettingsScreen>>,
+    board_assets: Res<BoardAssets>,
+    settings: Res<Settings>,
+    countdown_options: Res<CountdownOptions>,
+    rebinding: Res<Rebinding>,
+) {
+    if !screen.is_empty() {
+        // Rebuilt with the theme once it is switched
+        if board_assets.is_changed() {
+            for entity in screen.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+        return;
+    }
+    let font = &board_assets.bomb_counter_font;
+    let on_off = |value: bool| if value { "on" } else { "off" };
+    let countdown = if countdown_options.enabled {
+        countdown_options.budget.label()
+    } else {
+        "off"
+    };
+    let (width, height) = settings.window_size;
+    let options = &settings.board;
+    let pan_button = match settings.camera.pan_button {
+        Some(button) => format!("{:?} drag", button),
+        None => "space + drag".to_string(),
+    };
+    let buttons = [
+        (
+            MenuButton::ToggleSafeStart,
+            format!("Safe start: {}", on_off(options.safe_start)),
+        ),
+        (
+            MenuButton::ToggleChording,
+            format!("Chording: {}", on_off(options.chording)),
+        ),
+        (
+            MenuButton::ToggleQuestionMarks,
+            format!("Question marks: {}", on_off(options.question_marks)),
+        ),
+        (
+            MenuButton::CycleNumberRule,
+            format!("Numbers: {}", options.number_rule.label()),
+        ),
+        (MenuButton::CycleCountdown, format!("Countdown: {}", countdown)),
+        (MenuButton::CycleTheme, format!("Theme: {}", board_assets.label)),
+        (
+            MenuButton::CyclePalette,
+            format!(
+                "Palette: {}",
+                settings.numbers.palette.map_or("theme", |p| p.label())
+            ),
+        ),
+        (
+            MenuButton::CycleNumberCue,
+            format!("Number cues: {}", settings.numbers.cue.label()),
+        ),
+        (
+            MenuButton::CycleWindowSize,
+            format!("Window: {}x{}", width, height),
+        ),
+        (MenuButton::CyclePanButton, format!("Pan: {}", pan_button)),
+        (MenuButton::CycleSound, format!("Sound: {}", settings.sound.label())),
+        (
+            MenuButton::ToggleReduceMotion,
+            format!("Reduce motion: {}", on_off(settings.animation.reduce_motion)),
+        ),
+    ];
+
+    commands
+        .spawn_bundle(screen_bundle())
+        .insert(SettingsScreen)
+        .insert(Name::new("SettingsScreen"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle("Settings", font.clone(), 40.));
+            for (button, label) in buttons {
+                spawn_button(parent, button, &label, font);
+            }
+            for action in KeyAction::ALL {
+                let key = if rebinding.action != Some(action) {
+                    format!("{:?}", settings.key_bindings.get(action))
+                } else if let Some((key, user)) = rebinding.refused {
+                    format!("press a key, {:?} is used by {}", key, user)
+                } else {
+                    "press a key".to_string()
+                };
+                spawn_button(
+                    parent,
+                    MenuButton::Rebind(action),
+                    &format!("{}: {}", action.label(), key),
+                    font,
+                );
+            }
+            spawn_button(parent, MenuButton::Back, "Back", font);
+        });
+}
+
+/// Applies the settings screen buttons, writing the settings back on every change
+#[allow(clippy::too_many_arguments)]
+pub fn settings_action(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &MenuButton), ActiveButtons>,
+    screen: Query<Entity, With<SettingsScreen>>,
+    mut settings: ResMut<Settings>,
+    mut board_options: ResMut<BoardOptions>,
+    mut countdown_options: ResMut<CountdownOptions>,
+    mut camera_controls: ResMut<CameraControls>,
+    mut animation_options: ResMut<AnimationOptions>,
+    mut number_style: ResMut<NumberStyle>,
+    mut rebinding: ResMut<Rebinding>,
+    mut windows: ResMut<Windows>,
+    asset_server: Res<AssetServer>,
+    themes: Res<Themes>,
+    theme_indexes: Res<Assets<ThemeIndex>>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        // Toggles also apply to the current board options, the board being played included
+        match *button {
+            MenuButton::ToggleSafeStart => {
+                settings.board.safe_start = !settings.board.safe_start;
+                board_options.safe_start = settings.board.safe_start;
+            }
+            MenuButton::ToggleChording => {
+                settings.board.chording = !settings.board.chording;
+                board_options.chording = settings.board.chording;
+            }
+            MenuButton::ToggleQuestionMarks => {
+                settings.board.question_marks = !settings.board.question_marks;
+                board_options.question_marks = settings.board.question_marks;
+            }
+            MenuButton::CycleNumberRule => {
+                settings.board.number_rule = settings.board.number_rule.next();
+                board_options.number_rule = settings.board.number_rule;
+            }
+            MenuButton::CycleCountdown => {
+                settings.countdown.cycle();
+                *countdown_options = settings.countdown.clone();
+            }
+            MenuButton::CycleTheme => {
+                let next = theme_indexes.get(&themes.0).and_then(|index| index.next(&settings.theme));
+                match next {
+                    Some(theme) => settings.theme = theme.to_string(),
+                    None => {
+                        log::warn!("Theme index not loaded, keeping the {} theme", settings.theme);
+                        continue;
+                    }
+                }
+                commands.insert_resource(ActiveTheme(asset_server.load(&theme_path(&settings.theme))));
+            }
+            MenuButton::CycleWindowSize => {
+                settings.window_size = settings.next_window_size();
+                let (width, height) = settings.window_size;
+                windows.get_primary_mut().unwrap().set_resolution(width, height);
+            }
+            MenuButton::CyclePanButton => {
+                settings.camera.pan_button = settings.next_pan_button();
+                camera_controls.pan_button = settings.camera.pan_button;
+            }
+            MenuButton::CyclePalette => {
+                settings.numbers.palette = settings.next_palette();
+                number_style.palette = settings.numbers.palette;
+            }
+            MenuButton::CycleNumberCue => {
+                settings.numbers.cue = settings.numbers.cue.next();
+                number_style.cue = settings.numbers.cue;
+            }
+            MenuButton::CycleSound => settings.sound.cycle(),
+            MenuButton::ToggleReduceMotion => {
+                settings.animation.reduce_motion = !settings.animation.reduce_motion;
+                animation_options.reduce_motion = settings.animation.reduce_motion;
+            }
+            MenuButton::Rebind(action) => {
+                rebinding.action = Some(action);
+                rebinding.refused = None;
+            }
+            _ => continue,
+        }
+        settings.save();
+        for entity in screen.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+}
+
+/// Binds the next key pressed to the action picked on the settings screen, keys already in use
+/// are refused and shown on the screen
+pub fn rebind_system(
+    mut commands: Commands,
+    mut keys: ResMut<Input<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut settings: ResMut<Settings>,
+    screen: Query<Entity, With<SettingsScreen>>,
+) {
+    let action = match rebinding.action {
+        Some(a) => a,
+        None => return,
+    };
+    let key = match keys.get_just_pressed().next() {
+        Some(k) => *k,
+        None => return,
+    };
+    // The key must not trigger its previous action
+    keys.clear();
+    for entity in screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if let Some(user) = settings.key_conflict(action, key) {
+        log::warn!("{:?} is used by {}, not binding it to {}", key, user, action.label());
+        rebinding.refused = Some((key, user));
+        return;
+    }
+    log::info!("{} bound to {:?}", action.label(), key);
+    settings.key_bindings.set(action, key);
+    settings.save();
+    rebinding.action = None;
+    rebinding.refused = None;
+}
@@ -0,0 +1,93 @@
+use crate::resources::{Settings, ThemeSounds};
+use bevy::log;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use board_plugin::events::{
+    BoardCompletedEvent, BombExplosionEvent, TileMarkedEvent, TilesUncoveredEvent,
+    UncoverAdjacentTilesEvent,
+};
+use board_plugin::resources::{BoardAssets, SoundEffect, TileState};
+
+/// Loads the sounds of the theme whenever it changes
+pub fn load_theme_sounds(
+    asset_server: Res<AssetServer>,
+    board_assets: Option<Res<BoardAssets>>,
+    mut theme_sounds: ResMut<ThemeSounds>,
+) {
+    let board_assets = match board_assets {
+        Some(assets) if assets.is_changed() => assets,
+        _ => return,
+    };
+    theme_sounds.0 = board_assets
+        .sounds
+        .iter()
+        .map(|(effect, path)| (*effect, asset_server.load(path.as_str())))
+        .collect();
+}
+
+/// Plays the theme sounds of the board events of the frame, each effect once at most and not
+/// again before `SoundSettings::min_interval`
+#[allow(clippy::too_many_arguments)]
+pub fn sound_system(
+    audio: Res<Audio>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    theme_sounds: Res<ThemeSounds>,
+    mut last_played: Local<HashMap<SoundEffect, f64>>,
+    mut tiles_uncovered_evr: EventReader<TilesUncoveredEvent>,
+    mut tile_marked_evr: EventReader<TileMarkedEvent>,
+    mut uncover_adjacent_tiles_evr: EventReader<UncoverAdjacentTilesEvent>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    let mut effects = Vec::new();
+    if board_completed_evr.iter().count() > 0 {
+        effects.push(SoundEffect::Win);
+    }
+    if bomb_explosion_evr.iter().count() > 0 {
+        effects.push(SoundEffect::Explosion);
+    }
+    if uncover_adjacent_tiles_evr.iter().count() > 0 {
+        effects.push(SoundEffect::Chord);
+    }
+    for event in tile_marked_evr.iter() {
+        effects.push(match event.1 {
+            TileState::Flagged => SoundEffect::Flag,
+            _ => SoundEffect::Unflag,
+        });
+    }
+    for event in tiles_uncovered_evr.iter() {
+        effects.push(match event.count {
+            1 => SoundEffect::Reveal,
+            _ => SoundEffect::Cascade,
+        });
+    }
+    // The louder effects cover the reveals they come with
+    if effects
+        .iter()
+        .any(|e| matches!(e, SoundEffect::Win | SoundEffect::Explosion | SoundEffect::Chord))
+    {
+        effects.retain(|e| *e != SoundEffect::Reveal);
+    }
+
+    let sound = &settings.sound;
+    if sound.muted {
+        return;
+    }
+    let now = time.seconds_since_startup();
+    for effect in effects {
+        let recent = last_played
+            .get(&effect)
+            .is_some_and(|last| now - last < sound.min_interval as f64);
+        if recent {
+            continue;
+        }
+        let source = match theme_sounds.0.get(&effect) {
+            Some(source) => source.clone(),
+            None => continue,
+        };
+        log::debug!("Playing {:?}", effect);
+        audio.play_with_settings(source, PlaybackSettings::ONCE.with_volume(sound.volume));
+        last_played.insert(effect, now);
+    }
+}
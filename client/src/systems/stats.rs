@@ -0,0 +1,24 @@
+use crate::resources::GameStats;
+use bevy::prelude::*;
+use board_plugin::events::{
+    HintUsedEvent, TileMarkEvent, TileTriggerEvent, UncoverAdjacentTilesEvent,
+};
+use board_plugin::resources::Board;
+
+pub fn game_stats_system(
+    board: Option<Res<Board>>,
+    mut game_stats: ResMut<GameStats>,
+    mut hint_used_evr: EventReader<HintUsedEvent>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut uncover_adjacent_tiles_evr: EventReader<UncoverAdjacentTilesEvent>,
+) {
+    if board.is_some_and(|b| b.is_added()) {
+        *game_stats = GameStats::default();
+    }
+    game_stats.hints += hint_used_evr.iter().count() as u32;
+    let marks = tile_mark_evr.iter().count() as u32;
+    game_stats.flags += marks;
+    game_stats.clicks += marks
+        + (tile_trigger_evr.iter().count() + uncover_adjacent_tiles_evr.iter().count()) as u32;
+}
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+pub const BUTTON_COLOR: Color = Color::rgb(0.2, 0.2, 0.2);
+pub const HOVERED_BUTTON_COLOR: Color = Color::rgb(0.3, 0.3, 0.3);
+pub const PRESSED_BUTTON_COLOR: Color = Color::rgb(0.4, 0.6, 0.4);
+pub const DISABLED_BUTTON_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+
+/// Marks buttons that can't be clicked, they keep their color
+#[derive(Component)]
+pub struct Disabled;
+
+/// Buttons whose interaction changed, disabled ones left out
+pub type ActiveButtons = (Changed<Interaction>, Without<Disabled>);
+
+pub fn button_color_system(
+    mut buttons: Query<(&Interaction, &mut UiColor), (ActiveButtons, With<Button>)>,
+) {
+    for (interaction, mut color) in buttons.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => PRESSED_BUTTON_COLOR,
+            Interaction::Hovered => HOVERED_BUTTON_COLOR,
+            Interaction::None => BUTTON_COLOR,
+        }
+        .into();
+    }
+}
+
+pub fn text_bundle(value: impl Into<String>, font: Handle<Font>, font_size: f32) -> TextBundle {
+    TextBundle {
+        text: Text::with_section(
+            value,
+            TextStyle {
+                font,
+                font_size,
+                color: Color::WHITE,
+            },
+            Default::default(),
+        ),
+        ..Default::default()
+    }
+}
+
+pub fn button_bundle(width: f32, color: Color) -> ButtonBundle {
+    ButtonBundle {
+        style: Style {
+            size: Size::new(Val::Px(width), Val::Px(40.)),
+            margin: Rect::all(Val::Px(4.)),
+            padding: Rect::all(Val::Px(4.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        color: color.into(),
+        ..Default::default()
+    }
+}
+
+/// Full window column, centering its content
+pub fn screen_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+            flex_direction: FlexDirection::ColumnReverse,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        color: Color::rgba(0., 0., 0., 0.8).into(),
+        ..Default::default()
+    }
+}
+
+/// Column with a dark background, centered in a `screen_bundle` with no color
+pub fn panel_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::ColumnReverse,
+            align_items: AlignItems::Center,
+            padding: Rect::all(Val::Px(16.)),
+            ..Default::default()
+        },
+        color: Color::rgba(0., 0., 0., 0.8).into(),
+        ..Default::default()
+    }
+}
+
+pub fn row_bundle() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        color: Color::NONE.into(),
+        ..Default::default()
+    }
+}
+
+/// Despawns every entity of a screen when its state is left
+pub fn clear_screen<T: Component>(mut commands: Commands, screen: Query<Entity, With<T>>) {
+    for entity in screen.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}